@@ -0,0 +1,243 @@
+// "Change one letter" families: groups of words that are identical except at a few fixed
+// positions, e.g. bare, care, dare, fare all match the pattern "_are".
+
+use std::collections::HashMap;
+
+use keyboard::KeyboardLayout;
+use word_tools::solution::Solution;
+
+const WILDCARD: char = '_';
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Family {
+    // The shared letters with the varying positions replaced by '_'.
+    pub pattern: String,
+    pub positions: Vec<usize>,
+    pub words: Vec<String>,
+}
+
+impl Family {
+    // The letters at the wildcard positions of `word`.
+    pub fn varying(&self, word: &str) -> String {
+        let bytes = word.as_bytes();
+        self.positions.iter().map(|&p| bytes[p] as char).collect()
+    }
+
+    pub fn to_solution(&self) -> Solution {
+        Solution::new()
+            .text("pattern", &self.pattern)
+            .number("size", self.words.len())
+            .list("words", &self.words)
+            .list("varying", self.words.iter().map(|word| self.varying(word)))
+    }
+
+    fn with_words(&self, words: Vec<String>) -> Family {
+        Family {
+            pattern: self.pattern.clone(),
+            positions: self.positions.clone(),
+            words,
+        }
+    }
+}
+
+// Groups the words of length `len` by everything except the letters at `positions`. Words that
+// aren't plain ASCII are skipped. The families are sorted by pattern and the words in each family
+// are sorted alphabetically.
+pub fn find_families(words: &[String], len: usize, positions: &[usize]) -> Vec<Family> {
+    assert!(positions.iter().all(|&p| p < len));
+    let mut by_pattern: HashMap<String, Vec<String>> = HashMap::new();
+    for word in words {
+        if word.len() != len || !word.is_ascii() {
+            continue;
+        }
+        let pattern: String = word
+            .chars()
+            .enumerate()
+            .map(|(ii, ch)| {
+                if positions.contains(&ii) {
+                    WILDCARD
+                } else {
+                    ch
+                }
+            })
+            .collect();
+        by_pattern.entry(pattern).or_default().push(word.clone());
+    }
+
+    let mut out: Vec<Family> = by_pattern
+        .into_iter()
+        .map(|(pattern, mut words)| {
+            words.sort();
+            words.dedup();
+            Family {
+                pattern,
+                positions: positions.to_vec(),
+                words,
+            }
+        })
+        .collect();
+    out.sort_by(|l, r| l.pattern.cmp(&r.pattern));
+    out
+}
+
+fn is_vowel(letter: char) -> bool {
+    "aeiou".contains(letter.to_ascii_lowercase())
+}
+
+// Whether every letter of `next` comes right after the matching letter of `prev`.
+fn is_next(prev: &str, next: &str) -> bool {
+    prev.len() == next.len()
+        && prev
+            .bytes()
+            .zip(next.bytes())
+            .all(|(p, n)| p.checked_add(1) == Some(n))
+}
+
+// Restrictions on the letters that vary within a family.
+pub enum VaryingRule {
+    // Keep the whole family.
+    Any,
+    // Split the family by keyboard row; every varying letter of a word must be on the same row.
+    SameRow(KeyboardLayout),
+    // Keep only the words whose varying letters are all vowels.
+    Vowels,
+    // Split the family into runs whose varying letters are consecutive in the alphabet.
+    Consecutive,
+}
+
+impl VaryingRule {
+    // Applies the rule to `family`, which may split it into several smaller families.
+    pub fn apply(&self, family: &Family) -> Vec<Family> {
+        match self {
+            VaryingRule::Any => vec![family.clone()],
+            VaryingRule::SameRow(layout) => {
+                let mut by_row: HashMap<u8, Vec<String>> = HashMap::new();
+                for word in &family.words {
+                    let rows: Vec<Option<u8>> = family
+                        .varying(word)
+                        .chars()
+                        .map(|ch| layout.row(ch))
+                        .collect();
+                    if let Some(&Some(row)) = rows.first() {
+                        if rows.iter().all(|&r| r == Some(row)) {
+                            by_row.entry(row).or_default().push(word.clone());
+                        }
+                    }
+                }
+                let mut rows: Vec<(u8, Vec<String>)> = by_row.into_iter().collect();
+                rows.sort();
+                rows.into_iter()
+                    .map(|(_, words)| family.with_words(words))
+                    .collect()
+            }
+            VaryingRule::Vowels => {
+                let words = family
+                    .words
+                    .iter()
+                    .filter(|word| family.varying(word).chars().all(is_vowel))
+                    .cloned()
+                    .collect();
+                vec![family.with_words(words)]
+            }
+            VaryingRule::Consecutive => {
+                let mut runs: Vec<Vec<String>> = Vec::new();
+                let mut last = String::new();
+                for word in &family.words {
+                    let varying = family.varying(word);
+                    match runs.last_mut() {
+                        Some(run) if is_next(&last, &varying) => run.push(word.clone()),
+                        _ => runs.push(vec![word.clone()]),
+                    }
+                    last = varying;
+                }
+                runs.into_iter()
+                    .map(|words| family.with_words(words))
+                    .collect()
+            }
+        }
+    }
+}
+
+// Finds the families of `len` letter words that vary at `positions`, narrowed down by `rule`, and
+// keeps those with at least `min_size` words.
+pub fn matching_families(
+    words: &[String],
+    len: usize,
+    positions: &[usize],
+    rule: &VaryingRule,
+    min_size: usize,
+) -> Vec<Family> {
+    find_families(words, len, positions)
+        .iter()
+        .filter(|family| family.words.len() >= min_size)
+        .flat_map(|family| rule.apply(family))
+        .filter(|family| family.words.len() >= min_size)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_families() {
+        let dict = words(&["bare", "care", "core", "cure", "dare", "bat", "mare"]);
+        let families = find_families(&dict, 4, &[0]);
+        assert_eq!(families.len(), 3);
+        assert_eq!(families[0].pattern, "_are");
+        assert_eq!(families[0].words, words(&["bare", "care", "dare", "mare"]));
+        assert_eq!(
+            families[0].to_solution().to_csv(),
+            "_are,4,bare care dare mare,b c d m"
+        );
+
+        let families = find_families(&dict, 4, &[0, 1]);
+        assert_eq!(families.len(), 1);
+        assert_eq!(families[0].pattern, "__re");
+        assert_eq!(families[0].words.len(), 6);
+        assert_eq!(families[0].varying("core"), "co");
+    }
+
+    #[test]
+    fn test_same_row() {
+        let dict = words(&[
+            "bare", "care", "dare", "fare", "hare", "mare", "rare", "tare",
+        ]);
+        let family = &find_families(&dict, 4, &[0])[0];
+        let split = VaryingRule::SameRow(KeyboardLayout::qwerty()).apply(family);
+        assert_eq!(split.len(), 3);
+        assert_eq!(split[0].words, words(&["rare", "tare"]));
+        assert_eq!(split[1].words, words(&["dare", "fare", "hare"]));
+        assert_eq!(split[2].words, words(&["bare", "care", "mare"]));
+    }
+
+    #[test]
+    fn test_vowels_and_consecutive() {
+        let dict = words(&["bat", "bet", "bit", "but", "bbt", "bct", "bdt", "bzt"]);
+        let family = &find_families(&dict, 3, &[1])[0];
+
+        let vowels = VaryingRule::Vowels.apply(family);
+        assert_eq!(vowels[0].words, words(&["bat", "bet", "bit", "but"]));
+
+        let runs = VaryingRule::Consecutive.apply(family);
+        let sizes: Vec<usize> = runs.iter().map(|run| run.words.len()).collect();
+        assert_eq!(sizes, vec![5, 1, 1, 1]);
+        assert_eq!(runs[0].words, words(&["bat", "bbt", "bct", "bdt", "bet"]));
+    }
+
+    #[test]
+    fn test_matching_families() {
+        let dict = words(&[
+            "bare", "care", "dare", "fare", "hare", "tare", "bore", "core",
+        ]);
+        let rule = VaryingRule::SameRow(KeyboardLayout::qwerty());
+        let found = matching_families(&dict, 4, &[0], &rule, 3);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].words, words(&["dare", "fare", "hare"]));
+        assert!(matching_families(&dict, 4, &[0], &rule, 4).is_empty());
+    }
+}
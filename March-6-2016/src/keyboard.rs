@@ -0,0 +1,271 @@
+// Keyboard layouts for typing puzzles ("words typed with one row", "left hand only", ...).
+
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Finger {
+    Pinky,
+    Ring,
+    Middle,
+    Index,
+}
+
+// Where a letter lives on a layout. `label` is what is printed on the key, which is the letter
+// itself on a keyboard and the digit on a phone keypad. Rows are numbered from the top starting
+// at 1 and columns from the left starting at 0. Phone keypads don't have a hand or finger.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Key {
+    pub label: char,
+    pub row: u8,
+    pub column: u8,
+    pub hand: Option<Hand>,
+    pub finger: Option<Finger>,
+}
+
+#[derive(Clone)]
+pub struct KeyboardLayout {
+    pub name: &'static str,
+    keys: HashMap<char, Key>,
+}
+
+// Standard touch typing: the left hand covers columns 0 through 4 and the right hand the rest,
+// with both index fingers covering two columns each.
+fn touch_typing(column: u8) -> (Hand, Finger) {
+    match column {
+        0 => (Hand::Left, Finger::Pinky),
+        1 => (Hand::Left, Finger::Ring),
+        2 => (Hand::Left, Finger::Middle),
+        3 | 4 => (Hand::Left, Finger::Index),
+        5 | 6 => (Hand::Right, Finger::Index),
+        7 => (Hand::Right, Finger::Middle),
+        8 => (Hand::Right, Finger::Ring),
+        _ => (Hand::Right, Finger::Pinky),
+    }
+}
+
+impl KeyboardLayout {
+    // Builds a typewriter style layout from its three letter rows, top to bottom. Every character
+    // in a row takes up one column, so punctuation keys should be included to keep the letters in
+    // the right columns.
+    pub fn from_rows(name: &'static str, rows: &[&str]) -> KeyboardLayout {
+        let mut keys = HashMap::new();
+        for (row, letters) in rows.iter().enumerate() {
+            for (column, letter) in letters.chars().enumerate() {
+                let (hand, finger) = touch_typing(column as u8);
+                keys.insert(
+                    letter,
+                    Key {
+                        label: letter,
+                        row: row as u8 + 1,
+                        column: column as u8,
+                        hand: Some(hand),
+                        finger: Some(finger),
+                    },
+                );
+            }
+        }
+        KeyboardLayout { name, keys }
+    }
+
+    pub fn qwerty() -> KeyboardLayout {
+        KeyboardLayout::from_rows("qwerty", &["qwertyuiop", "asdfghjkl;", "zxcvbnm,./"])
+    }
+
+    pub fn dvorak() -> KeyboardLayout {
+        KeyboardLayout::from_rows("dvorak", &["',.pyfgcrl", "aoeuidhtns", ";qjkxbmwvz"])
+    }
+
+    pub fn colemak() -> KeyboardLayout {
+        KeyboardLayout::from_rows("colemak", &["qwfpgjluy;", "arstdhneio", "zxcvbkm,./"])
+    }
+
+    pub fn azerty() -> KeyboardLayout {
+        KeyboardLayout::from_rows("azerty", &["azertyuiop", "qsdfghjklm", "wxcvbn,;:!"])
+    }
+
+    // The letters on a standard phone keypad. Row 1 is the "1 2 3" row.
+    pub fn phone_keypad() -> KeyboardLayout {
+        let keys_by_digit = [
+            ('2', "abc"),
+            ('3', "def"),
+            ('4', "ghi"),
+            ('5', "jkl"),
+            ('6', "mno"),
+            ('7', "pqrs"),
+            ('8', "tuv"),
+            ('9', "wxyz"),
+        ];
+        let mut keys = HashMap::new();
+        for &(digit, letters) in keys_by_digit.iter() {
+            let index = digit.to_digit(10).unwrap() as u8 - 1;
+            for letter in letters.chars() {
+                keys.insert(
+                    letter,
+                    Key {
+                        label: digit,
+                        row: index / 3 + 1,
+                        column: index % 3,
+                        hand: None,
+                        finger: None,
+                    },
+                );
+            }
+        }
+        KeyboardLayout {
+            name: "phone",
+            keys,
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<KeyboardLayout> {
+        match name {
+            "qwerty" => Some(KeyboardLayout::qwerty()),
+            "dvorak" => Some(KeyboardLayout::dvorak()),
+            "colemak" => Some(KeyboardLayout::colemak()),
+            "azerty" => Some(KeyboardLayout::azerty()),
+            "phone" => Some(KeyboardLayout::phone_keypad()),
+            _ => None,
+        }
+    }
+
+    pub fn key(&self, letter: char) -> Option<&Key> {
+        self.keys.get(&letter.to_ascii_lowercase())
+    }
+
+    pub fn row(&self, letter: char) -> Option<u8> {
+        self.key(letter).map(|key| key.row)
+    }
+
+    pub fn hand(&self, letter: char) -> Option<Hand> {
+        self.key(letter).and_then(|key| key.hand)
+    }
+
+    pub fn finger(&self, letter: char) -> Option<Finger> {
+        self.key(letter).and_then(|key| key.finger)
+    }
+
+    // Returns the (row, column) of the key for `letter`.
+    pub fn coordinates(&self, letter: char) -> Option<(u8, u8)> {
+        self.key(letter).map(|key| (key.row, key.column))
+    }
+
+    // Looks up the keys for every letter in `word`. Returns None if any letter isn't on the layout.
+    pub fn keys(&self, word: &str) -> Option<Vec<&Key>> {
+        word.chars().map(|letter| self.key(letter)).collect()
+    }
+}
+
+// The usual typing puzzle constraints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypingRule {
+    // Every letter is on the same row.
+    OneRow,
+    // Every letter is on the given row.
+    Row(u8),
+    // Every letter is typed by the same hand.
+    OneHand,
+    // Every letter is typed by the given hand.
+    Hand(Hand),
+    // Consecutive letters are typed by different hands.
+    AlternatingHands,
+}
+
+impl TypingRule {
+    pub fn parse(rule: &str) -> Option<TypingRule> {
+        match rule {
+            "one-row" => Some(TypingRule::OneRow),
+            "top-row" => Some(TypingRule::Row(1)),
+            "home-row" => Some(TypingRule::Row(2)),
+            "bottom-row" => Some(TypingRule::Row(3)),
+            "one-hand" => Some(TypingRule::OneHand),
+            "left-hand" => Some(TypingRule::Hand(Hand::Left)),
+            "right-hand" => Some(TypingRule::Hand(Hand::Right)),
+            "alternating-hands" => Some(TypingRule::AlternatingHands),
+            _ => None,
+        }
+    }
+
+    // Checks whether `word` can be typed on `layout` following the rule. Words with letters
+    // missing from the layout never match.
+    pub fn matches(&self, layout: &KeyboardLayout, word: &str) -> bool {
+        let keys = match layout.keys(word) {
+            Some(keys) => keys,
+            None => return false,
+        };
+        if keys.is_empty() {
+            return false;
+        }
+        match *self {
+            TypingRule::OneRow => keys.iter().all(|key| key.row == keys[0].row),
+            TypingRule::Row(row) => keys.iter().all(|key| key.row == row),
+            TypingRule::OneHand => {
+                keys[0].hand.is_some() && keys.iter().all(|key| key.hand == keys[0].hand)
+            }
+            TypingRule::Hand(hand) => keys.iter().all(|key| key.hand == Some(hand)),
+            TypingRule::AlternatingHands => {
+                keys.iter().all(|key| key.hand.is_some())
+                    && keys.windows(2).all(|pair| pair[0].hand != pair[1].hand)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qwerty() {
+        let qwerty = KeyboardLayout::qwerty();
+        assert_eq!(qwerty.row('q'), Some(1));
+        assert_eq!(qwerty.row('L'), Some(2));
+        assert_eq!(qwerty.row('m'), Some(3));
+        assert_eq!(qwerty.row('1'), None);
+        assert_eq!(qwerty.hand('t'), Some(Hand::Left));
+        assert_eq!(qwerty.hand('y'), Some(Hand::Right));
+        assert_eq!(qwerty.finger('a'), Some(Finger::Pinky));
+        assert_eq!(qwerty.finger('j'), Some(Finger::Index));
+        assert_eq!(qwerty.coordinates('c'), Some((3, 2)));
+    }
+
+    #[test]
+    fn test_other_layouts() {
+        let dvorak = KeyboardLayout::dvorak();
+        assert_eq!(dvorak.coordinates('p'), Some((1, 3)));
+        assert_eq!(dvorak.hand('u'), Some(Hand::Left));
+
+        let colemak = KeyboardLayout::colemak();
+        assert_eq!(colemak.row('o'), Some(2));
+
+        let azerty = KeyboardLayout::azerty();
+        assert_eq!(azerty.row('m'), Some(2));
+        assert_eq!(azerty.row('w'), Some(3));
+
+        let phone = KeyboardLayout::phone_keypad();
+        assert_eq!(phone.key('s').unwrap().label, '7');
+        assert_eq!(phone.coordinates('a'), Some((1, 1)));
+        assert_eq!(phone.coordinates('w'), Some((3, 2)));
+        assert_eq!(phone.hand('a'), None);
+    }
+
+    #[test]
+    fn test_typing_rules() {
+        let qwerty = KeyboardLayout::qwerty();
+        assert!(TypingRule::OneRow.matches(&qwerty, "typewriter"));
+        assert!(!TypingRule::OneRow.matches(&qwerty, "keyboard"));
+        assert!(TypingRule::Row(2).matches(&qwerty, "flask"));
+        assert!(TypingRule::OneHand.matches(&qwerty, "stewardesses"));
+        assert!(TypingRule::Hand(Hand::Right).matches(&qwerty, "lollipop"));
+        assert!(!TypingRule::Hand(Hand::Left).matches(&qwerty, "lollipop"));
+        assert!(TypingRule::AlternatingHands.matches(&qwerty, "authenticity"));
+        assert!(!TypingRule::AlternatingHands.matches(&qwerty, "keyboard"));
+        assert!(!TypingRule::OneRow.matches(&qwerty, "don't"));
+        assert!(!TypingRule::OneHand.matches(&KeyboardLayout::phone_keypad(), "abc"));
+    }
+}
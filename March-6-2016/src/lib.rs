@@ -0,0 +1,5 @@
+extern crate word_tools;
+
+pub mod families;
+pub mod keyboard;
+pub mod t9;
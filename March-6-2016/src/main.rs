@@ -1,61 +1,212 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::vec::Vec;
-
-fn collect_matching_words(mut words: Vec<String>) -> HashMap<String, Vec<String>> {
-    let mut collected_words = HashMap::new();
-    for word in words.drain(..) {
-        let (prefix, suffix) = word.split_at(1);
-        collected_words.entry(suffix.to_owned()).or_insert(Vec::new()).push(prefix.to_owned())
+use std::env;
+
+extern crate march_6_2016;
+use march_6_2016::families::{matching_families, VaryingRule};
+use march_6_2016::keyboard::{KeyboardLayout, TypingRule};
+use march_6_2016::t9::{parse_keys, T9Index};
+
+extern crate word_tools;
+use word_tools::dictionary::{load_words, WordFilter, FILTER_USAGE};
+use word_tools::flags;
+use word_tools::solution::{stdout_writer, OutputFormat, Solution, OUTPUT_USAGE};
+use word_tools::stats::{Stats, StatsFormat, STATS_USAGE};
+
+const USAGE: &str = "Usage: March-6-2016 [options]
+By default this solves the original puzzle: five or more four letter words that differ only in the
+first letter, where the differing letters are all on the same keyboard row.
+  --layout NAME         qwerty (default), dvorak, colemak, azerty or phone
+  --typed RULE          List the words that can be typed following RULE instead: one-row, top-row,
+                        home-row, bottom-row, one-hand, left-hand, right-hand or alternating-hands
+  --length N            Word length (default 4)
+  --positions P1,P2,..  Zero based positions of the letters that vary (default 0)
+  --rule RULE           same-row (default), vowels, consecutive or any
+  --min-size N          Smallest family to report (default 5)
+  --phone QUERY         Phone keypad puzzles instead: same lists words that spell the same number,
+                        number:DIGITS the words that spell DIGITS and keys:SET the words typed only
+                        with keys in SET (e.g. 2-4)";
+
+// What --phone asks about the words.
+enum PhoneQuery {
+    Same,
+    Number(String),
+    Keys(Vec<char>),
+}
+
+impl PhoneQuery {
+    fn parse(query: &str) -> Option<PhoneQuery> {
+        if query == "same" {
+            Some(PhoneQuery::Same)
+        } else if let Some(keys) = query.strip_prefix("keys:") {
+            Some(PhoneQuery::Keys(parse_keys(keys)))
+        } else {
+            query
+                .strip_prefix("number:")
+                .map(|digits| PhoneQuery::Number(digits.to_string()))
+        }
     }
-    collected_words
 }
 
-fn find_row(letter: &str) -> i8 {
-    static first: [&'static str; 10] = ["q", "w", "e", "r", "t", "y", "u", "i", "o", "p"];
-    static second: [&'static str; 9] = ["a", "s", "d", "f", "g", "h", "j", "k", "l"];
-    static third: [&'static str; 7] = ["z", "x", "c", "v", "b", "n", "m"];
-    if first.contains(&letter) {
-        return 1;
-    } else if second.contains(&letter) {
-        return 2;
-    } else if third.contains(&letter) {
-        return 3;
+// The --rule called `name`, splitting families by rows of `layout` for same-row.
+fn varying_rule(name: &str, layout: &KeyboardLayout) -> Option<VaryingRule> {
+    match name {
+        "same-row" => Some(VaryingRule::SameRow(layout.clone())),
+        "vowels" => Some(VaryingRule::Vowels),
+        "consecutive" => Some(VaryingRule::Consecutive),
+        "any" => Some(VaryingRule::Any),
+        _ => None,
     }
-    unreachable!()
+}
+
+struct Config {
+    layout: KeyboardLayout,
+    typing_rule: Option<TypingRule>,
+    length: usize,
+    positions: Vec<usize>,
+    rule: VaryingRule,
+    min_size: usize,
+    phone_query: Option<PhoneQuery>,
+    filter: WordFilter,
+    output: OutputFormat,
+    stats: StatsFormat,
+}
+
+fn parse_args() -> Result<Config, String> {
+    let mut layout = KeyboardLayout::qwerty();
+    let mut typing_rule = None;
+    let mut length = 4;
+    let mut positions = vec![0];
+    let mut rule_name = "same-row".to_string();
+    let mut min_size = 5;
+    let mut phone_query = None;
+    let mut filter = WordFilter::default();
+    let mut output = OutputFormat::default();
+    let mut stats = StatsFormat::default();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if filter.parse_arg(&arg, &mut args)?
+            || output.parse_arg(&arg, &mut args)?
+            || stats.parse_arg(&arg, &mut args)?
+        {
+            continue;
+        }
+        match arg.as_str() {
+            "--layout" => layout = flags::named(&arg, &mut args, KeyboardLayout::by_name)?,
+            "--typed" => typing_rule = Some(flags::named(&arg, &mut args, TypingRule::parse)?),
+            "--length" => length = flags::number(&arg, &mut args)?,
+            "--positions" => {
+                positions = flags::value(&arg, &mut args)?
+                    .split(',')
+                    .map(|p| {
+                        p.parse()
+                            .map_err(|why| format!("Bad value for {}: {}", arg, why))
+                    })
+                    .collect::<Result<_, _>>()?
+            }
+            "--rule" => {
+                rule_name = flags::named(&arg, &mut args, |name| {
+                    varying_rule(name, &layout).map(|_| name.to_string())
+                })?
+            }
+            "--min-size" => min_size = flags::number(&arg, &mut args)?,
+            "--phone" => phone_query = Some(flags::named(&arg, &mut args, PhoneQuery::parse)?),
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+    // `find_families` asserts this, so catch it here with a message instead.
+    if let Some(position) = positions.iter().find(|&&p| p >= length) {
+        return Err(format!(
+            "Position {} is past the end of a {} letter word",
+            position, length
+        ));
+    }
+    // --layout may come after --rule, so same-row only gets its layout now.
+    let rule = varying_rule(&rule_name, &layout).unwrap();
+    Ok(Config {
+        layout,
+        typing_rule,
+        length,
+        positions,
+        rule,
+        min_size,
+        phone_query,
+        filter,
+        output,
+        stats,
+    })
 }
 
 fn main() {
-    let f = match File::open("some_words.txt") {
-        Ok(f) => f,
-        Err(why) => panic!("Failed to open links.txt: {}", Error::description(&why)),
+    let config = match parse_args() {
+        Ok(config) => config,
+        Err(why) => {
+            eprintln!(
+                "{}\n{}\n{}\n{}\n{}",
+                why, USAGE, FILTER_USAGE, OUTPUT_USAGE, STATS_USAGE
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let mut stats = Stats::new();
+    let words = load_words("some_words.txt", &config.filter);
+    stats.record("dictionary", words.len(), words.len());
+    let mut writer = stdout_writer(config.output);
+    let mut emit = |text: String, solution: Solution| match &mut writer {
+        Some(writer) => writer.write(&solution).unwrap(),
+        None => println!("{}", text),
     };
-    let words: Vec<String> = BufReader::new(f)
-                                 .lines()
-                                 .map(|s| s.unwrap())
-                                 .filter(|s| s.len() == 4)
-                                 .map(|s| s)
-                                 .collect();
-    let suffix_to_prefix: HashMap<String, Vec<String>> = collect_matching_words(words)
-                                                             .into_iter()
-                                                             .filter(|&(_, ref v)| v.len() > 4)
-                                                             .collect();
-    for (suffix, prefixes) in suffix_to_prefix {
-        let mut rows_map: HashMap<i8, i8> = HashMap::new();
-        for prefix in prefixes.iter() {
-            *rows_map.entry(find_row(&prefix)).or_insert(0) += 1;
+    if let Some(rule) = config.typing_rule {
+        let typed: Vec<&String> = words
+            .iter()
+            .filter(|s| rule.matches(&config.layout, s))
+            .collect();
+        stats.record("typed", words.len(), typed.len());
+        for word in typed {
+            emit(word.clone(), Solution::new().text("word", word));
         }
-        for (row, count) in rows_map {
-            if count >= 5 {
-                println!("Found:");
-                for prefix in prefixes.iter() {
-                    if find_row(&prefix) == row {
-                        println!("{}{}", prefix, suffix);
-                    }
+        stats.report(config.stats);
+        return;
+    }
+
+    if let Some(query) = &config.phone_query {
+        let index = T9Index::new(&words);
+        let found = match query {
+            PhoneQuery::Same => index.shared_numbers(2),
+            PhoneQuery::Keys(keys) => index.using_only(keys),
+            PhoneQuery::Number(digits) => {
+                let spelled = index.words_for(digits);
+                stats.record("phone", words.len(), spelled.len());
+                for word in spelled {
+                    let solution = Solution::new().text("digits", digits).text("word", word);
+                    emit(word.clone(), solution);
                 }
+                stats.report(config.stats);
+                return;
             }
+        };
+        stats.record("phone", words.len(), found.len());
+        for (digits, words) in found {
+            let solution = Solution::new().text("digits", digits).list("words", words);
+            emit(format!("{}: {}", digits, words.join(" ")), solution);
+        }
+        stats.report(config.stats);
+        return;
+    }
+
+    let families = matching_families(
+        &words,
+        config.length,
+        &config.positions,
+        &config.rule,
+        config.min_size,
+    );
+    stats.record("families", words.len(), families.len());
+    for family in families {
+        let mut text = format!("Found {} ({} words):", family.pattern, family.words.len());
+        for word in &family.words {
+            text += &format!("\n{}", word);
         }
+        emit(text, family.to_solution());
     }
+    stats.report(config.stats);
 }
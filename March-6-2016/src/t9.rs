@@ -0,0 +1,117 @@
+// Phone keypad puzzles: words that spell the same number, words that spell a given number and
+// words typed with only a few keys.
+
+use std::collections::BTreeMap;
+
+use keyboard::KeyboardLayout;
+
+// Converts `word` to the digits pressed to type it on a phone keypad. Returns None if a letter
+// isn't on the keypad.
+pub fn to_digits(keypad: &KeyboardLayout, word: &str) -> Option<String> {
+    word.chars()
+        .map(|letter| keypad.key(letter).map(|key| key.label))
+        .collect()
+}
+
+// Parses a set of keys such as "2-4" or "279" into the digits it contains.
+pub fn parse_keys(keys: &str) -> Vec<char> {
+    let chars: Vec<char> = keys.chars().collect();
+    let mut out = Vec::new();
+    let mut ii = 0;
+    while ii < chars.len() {
+        if ii + 2 < chars.len() && chars[ii + 1] == '-' {
+            out.extend(chars[ii]..=chars[ii + 2]);
+            ii += 3;
+        } else {
+            out.push(chars[ii]);
+            ii += 1;
+        }
+    }
+    out
+}
+
+// Dictionary words grouped by the number they spell.
+pub struct T9Index {
+    by_digits: BTreeMap<String, Vec<String>>,
+}
+
+impl T9Index {
+    pub fn new(words: &[String]) -> T9Index {
+        let keypad = KeyboardLayout::phone_keypad();
+        let mut by_digits: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for word in words {
+            if let Some(digits) = to_digits(&keypad, word) {
+                by_digits.entry(digits).or_default().push(word.clone());
+            }
+        }
+        T9Index { by_digits }
+    }
+
+    // The words that spell `digits`.
+    pub fn words_for(&self, digits: &str) -> &[String] {
+        self.by_digits
+            .get(digits)
+            .map_or(&[], |words| words.as_slice())
+    }
+
+    // Numbers spelled by at least `min_words` different words.
+    pub fn shared_numbers(&self, min_words: usize) -> Vec<(&String, &Vec<String>)> {
+        self.by_digits
+            .iter()
+            .filter(|(_, words)| words.len() >= min_words)
+            .collect()
+    }
+
+    // Words typed using only the keys in `keys`.
+    pub fn using_only(&self, keys: &[char]) -> Vec<(&String, &Vec<String>)> {
+        self.by_digits
+            .iter()
+            .filter(|(digits, _)| digits.chars().all(|digit| keys.contains(&digit)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_to_digits() {
+        let keypad = KeyboardLayout::phone_keypad();
+        assert_eq!(to_digits(&keypad, "home"), Some("4663".to_string()));
+        assert_eq!(to_digits(&keypad, "Good"), Some("4663".to_string()));
+        assert_eq!(to_digits(&keypad, "it's"), None);
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        assert_eq!(parse_keys("2-4"), vec!['2', '3', '4']);
+        assert_eq!(parse_keys("279"), vec!['2', '7', '9']);
+        assert_eq!(parse_keys("2-36"), vec!['2', '3', '6']);
+    }
+
+    #[test]
+    fn test_index() {
+        let index = T9Index::new(&words(&[
+            "home", "good", "gone", "hood", "cab", "bad", "ace",
+        ]));
+        assert_eq!(
+            index.words_for("4663"),
+            &words(&["home", "good", "gone", "hood"])[..]
+        );
+        assert!(index.words_for("1234").is_empty());
+
+        assert_eq!(index.shared_numbers(2).len(), 2);
+        let shared = index.shared_numbers(3);
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].0, "4663");
+
+        let cheap = index.using_only(&parse_keys("2-3"));
+        let cheap: Vec<&String> = cheap.iter().flat_map(|(_, words)| words.iter()).collect();
+        assert_eq!(cheap, vec!["cab", "bad", "ace"]);
+    }
+}
@@ -0,0 +1,177 @@
+// Questions about the edit graph as a whole: which words are connected, which two words are
+// farthest apart, which words have the most neighbors and which have none.
+
+use std::collections::VecDeque;
+
+use crate::ladder::EditGraph;
+
+// Breadth first search from `start`. Returns the distance to every word, or None if it can't be
+// reached.
+fn distances(graph: &EditGraph, start: usize) -> Vec<Option<usize>> {
+    let mut dist = vec![None; graph.words.len()];
+    dist[start] = Some(0);
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+    while let Some(id) = queue.pop_front() {
+        let next = dist[id].unwrap() + 1;
+        for &neighbor in graph.neighbors(id) {
+            if dist[neighbor].is_none() {
+                dist[neighbor] = Some(next);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    dist
+}
+
+// Groups the words into connected components, largest first. Ties keep dictionary order.
+pub fn components(graph: &EditGraph) -> Vec<Vec<usize>> {
+    let mut seen = vec![false; graph.words.len()];
+    let mut out = Vec::new();
+    for start in 0..graph.words.len() {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut component = vec![start];
+        let mut ii = 0;
+        while ii < component.len() {
+            for &neighbor in graph.neighbors(component[ii]) {
+                if !seen[neighbor] {
+                    seen[neighbor] = true;
+                    component.push(neighbor);
+                }
+            }
+            ii += 1;
+        }
+        component.sort_unstable();
+        out.push(component);
+    }
+    out.sort_by_key(|component| std::cmp::Reverse(component.len()));
+    out
+}
+
+// The two words in `component` with the longest shortest path between them, along with that
+// distance. This runs a breadth first search from every word, so it is quadratic in the size of
+// the component.
+pub fn diameter(graph: &EditGraph, component: &[usize]) -> Option<(usize, usize, usize)> {
+    let mut best: Option<(usize, usize, usize)> = None;
+    for &from in component {
+        let dist = distances(graph, from);
+        for &to in component {
+            if let Some(d) = dist[to] {
+                if best.is_none_or(|(_, _, longest)| d > longest) {
+                    best = Some((from, to, d));
+                }
+            }
+        }
+    }
+    best
+}
+
+// The two connected words that are farthest apart, along with how far. The largest component
+// doesn't always have the longest path, so every component is searched, skipping the ones too
+// small to beat the longest path found so far.
+pub fn graph_diameter(graph: &EditGraph) -> Option<(usize, usize, usize)> {
+    let mut best: Option<(usize, usize, usize)> = None;
+    for component in components(graph) {
+        // A path visits each word at most once. Components are largest first, so the rest are
+        // too small as well.
+        if component.len() < 2 || best.is_some_and(|(_, _, longest)| component.len() - 1 <= longest)
+        {
+            break;
+        }
+        let found = diameter(graph, &component);
+        if found.is_some_and(|(_, _, d)| best.is_none_or(|(_, _, longest)| d > longest)) {
+            best = found;
+        }
+    }
+    best
+}
+
+// The `count` words with the most neighbors, most connected first.
+pub fn hubs(graph: &EditGraph, count: usize) -> Vec<(usize, usize)> {
+    let mut degrees: Vec<(usize, usize)> = (0..graph.words.len())
+        .map(|id| (id, graph.neighbors(id).len()))
+        .filter(|&(_, degree)| degree > 0)
+        .collect();
+    degrees.sort_by_key(|&(id, degree)| (std::cmp::Reverse(degree), id));
+    degrees.truncate(count);
+    degrees
+}
+
+// Words without a single neighbor.
+pub fn isolated(graph: &EditGraph) -> Vec<usize> {
+    (0..graph.words.len())
+        .filter(|&id| graph.neighbors(id).is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ladder::{EdgeTypes, LadderRules};
+
+    fn graph(list: &[&str]) -> EditGraph {
+        let words: Vec<Vec<u8>> = list.iter().map(|s| s.as_bytes().to_vec()).collect();
+        let edges = EdgeTypes::parse("substitute").unwrap();
+        EditGraph::new(&words, &edges, &LadderRules::default())
+    }
+
+    fn names(graph: &EditGraph, ids: &[usize]) -> Vec<String> {
+        ids.iter().map(|&id| graph.word(id).to_string()).collect()
+    }
+
+    const WORDS: &[&str] = &[
+        "cold", "cord", "card", "ward", "warm", "word", "zebra", "quiz", "quit", "suit",
+    ];
+
+    #[test]
+    fn test_components() {
+        let graph = graph(WORDS);
+        let found = components(&graph);
+        let sizes: Vec<usize> = found.iter().map(|c| c.len()).collect();
+        assert_eq!(sizes, vec![6, 3, 1]);
+        assert_eq!(names(&graph, &found[1]), vec!["quiz", "quit", "suit"]);
+        assert_eq!(names(&graph, &found[2]), vec!["zebra"]);
+    }
+
+    #[test]
+    fn test_diameter() {
+        let graph = graph(WORDS);
+        let found = components(&graph);
+        let (from, to, distance) = diameter(&graph, &found[0]).unwrap();
+        assert_eq!(
+            (graph.word(from), graph.word(to), distance),
+            ("cold", "warm", 4)
+        );
+        assert_eq!(diameter(&graph, &found[2]).unwrap().2, 0);
+        assert_eq!(diameter(&graph, &[]), None);
+    }
+
+    #[test]
+    fn test_graph_diameter() {
+        assert_eq!(graph_diameter(&graph(&["zebra", "quiz"])), None);
+        // Every word in the largest component is one step from the others, while the chain from
+        // dog to pit takes four.
+        let graph = graph(&[
+            "bat", "cat", "hat", "mat", "rat", "sat", "dog", "dot", "lot", "lit", "pit", "zebra",
+        ]);
+        let largest = &components(&graph)[0];
+        assert_eq!(diameter(&graph, largest).unwrap().2, 1);
+        let (from, to, distance) = graph_diameter(&graph).unwrap();
+        assert_eq!(
+            (graph.word(from), graph.word(to), distance),
+            ("dog", "pit", 4)
+        );
+    }
+
+    #[test]
+    fn test_hubs_and_isolated() {
+        let graph = graph(WORDS);
+        let top = hubs(&graph, 2);
+        let top: Vec<(&str, usize)> = top.iter().map(|&(id, d)| (graph.word(id), d)).collect();
+        assert_eq!(top, vec![("cord", 3), ("ward", 3)]);
+        assert_eq!(names(&graph, &isolated(&graph)), vec!["zebra"]);
+    }
+}
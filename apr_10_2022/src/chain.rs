@@ -0,0 +1,605 @@
+// Chains of words where each word is the previous one with a single letter inserted at a fixed
+// position, e.g. "reach" -> "breach" -> "breaches" style puzzles.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use word_tools::near::{NearMiss, Rule, Violation};
+use word_tools::stats::Stats;
+use word_tools::uniqueness::{unique_hints, Uniqueness};
+
+use crate::delete_at;
+
+// Describes a chain: the length of the first word, then for each step the (zero based) position
+// in the longer word where the new letter goes. Every word in the chain must contain
+// `required_letter` if it is set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainSpec {
+    pub start_len: usize,
+    pub positions: Vec<usize>,
+    pub required_letter: Option<u8>,
+}
+
+impl ChainSpec {
+    // The number of words in a complete chain.
+    pub fn chain_len(&self) -> usize {
+        self.positions.len() + 1
+    }
+
+    fn allows(&self, word: &[u8]) -> bool {
+        match self.required_letter {
+            Some(letter) => word.contains(&letter),
+            None => true,
+        }
+    }
+}
+
+impl Default for ChainSpec {
+    // The Apr 10, 2022 puzzle: a five letter word, add a letter to the front, then add a letter
+    // in the fifth position. Every word contains an 'l'.
+    fn default() -> Self {
+        ChainSpec {
+            start_len: 5,
+            positions: vec![0, 4],
+            required_letter: Some(b'l'),
+        }
+    }
+}
+
+// Maps each word with the letter at one position deleted to the words that produced it, so that
+// looking up every way to insert a letter at that position is a single hash lookup.
+pub struct DeletionIndex<'a> {
+    map: HashMap<Vec<u8>, Vec<&'a [u8]>>,
+}
+
+impl<'a> DeletionIndex<'a> {
+    // Indexes every word in `words` of length `len` at `position`.
+    pub fn new<I>(words: I, len: usize, position: usize) -> DeletionIndex<'a>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        let mut map: HashMap<Vec<u8>, Vec<&'a [u8]>> = HashMap::new();
+        for word in words {
+            if word.len() != len || position >= len {
+                continue;
+            }
+            map.entry(delete_at(word, position)).or_default().push(word);
+        }
+        DeletionIndex { map }
+    }
+
+    // The indexed words that are `word` with a letter inserted at the indexed position.
+    pub fn insertions(&self, word: &[u8]) -> &[&'a [u8]] {
+        self.map.get(word).map_or(&[], |words| words.as_slice())
+    }
+}
+
+// Finds chains following `spec`, one step at a time. The returned vector has an entry per chain
+// length starting at two words, so callers can report how many partial chains survive each step.
+// Chains are in dictionary order.
+pub fn find_chains<'a>(words: &'a [Vec<u8>], spec: &ChainSpec) -> Vec<Vec<Vec<&'a [u8]>>> {
+    chain_steps(words, spec, None)
+}
+
+// `find_chains`, recording how many words or chains each step keeps in `stats`. Recording reads
+// the clock and the peak memory, so only use this for --stats.
+pub fn counted_chains<'a>(
+    words: &'a [Vec<u8>],
+    spec: &ChainSpec,
+    stats: &mut Stats,
+) -> Vec<Vec<Vec<&'a [u8]>>> {
+    chain_steps(words, spec, Some(stats))
+}
+
+fn chain_steps<'a>(
+    words: &'a [Vec<u8>],
+    spec: &ChainSpec,
+    mut stats: Option<&mut Stats>,
+) -> Vec<Vec<Vec<&'a [u8]>>> {
+    let mut record = |name: &str, input: usize, output: usize| {
+        if let Some(stats) = stats.as_deref_mut() {
+            stats.record(name, input, output);
+        }
+    };
+    let allowed: Vec<&'a [u8]> = words
+        .iter()
+        .map(|w| w.as_slice())
+        .filter(|w| spec.allows(w))
+        .collect();
+    record("required letter", words.len(), allowed.len());
+
+    let mut chains: Vec<Vec<&'a [u8]>> = allowed
+        .iter()
+        .filter(|w| w.len() == spec.start_len)
+        .map(|w| vec![*w])
+        .collect();
+    record("first words", allowed.len(), chains.len());
+
+    let mut out = Vec::new();
+    for (step, &position) in spec.positions.iter().enumerate() {
+        let len = spec.start_len + step + 1;
+        let index = DeletionIndex::new(allowed.iter().cloned(), len, position);
+        let mut next = Vec::new();
+        for chain in &chains {
+            for longer in index.insertions(chain.last().unwrap()) {
+                let mut extended = chain.clone();
+                extended.push(*longer);
+                next.push(extended);
+            }
+        }
+        let name = format!("step {}: insert at {}", step + 1, position);
+        record(&name, chains.len(), next.len());
+        chains = next;
+        out.push(chains.clone());
+    }
+    out
+}
+
+// Finds up to `limit` complete chains following `spec`, depth first so that it can stop as soon
+// as it has them. They come in the same order as the last step of `find_chains`.
+pub fn first_chains<'a>(
+    words: &'a [Vec<u8>],
+    spec: &ChainSpec,
+    limit: usize,
+) -> Vec<Vec<&'a [u8]>> {
+    let allowed: Vec<&'a [u8]> = words
+        .iter()
+        .map(|w| w.as_slice())
+        .filter(|w| spec.allows(w))
+        .collect();
+    let indexes: Vec<DeletionIndex<'a>> = spec
+        .positions
+        .iter()
+        .enumerate()
+        .map(|(step, &position)| {
+            DeletionIndex::new(allowed.iter().cloned(), spec.start_len + step + 1, position)
+        })
+        .collect();
+
+    let mut out = Vec::new();
+    for &first in allowed.iter().filter(|w| w.len() == spec.start_len) {
+        if out.len() >= limit {
+            break;
+        }
+        extend(&indexes, &mut vec![first], limit, &mut out);
+    }
+    out
+}
+
+// Adds the chains that continue `chain` to `out`, until there are `limit` of them.
+fn extend<'a>(
+    indexes: &[DeletionIndex<'a>],
+    chain: &mut Vec<&'a [u8]>,
+    limit: usize,
+    out: &mut Vec<Vec<&'a [u8]>>,
+) {
+    let step = chain.len() - 1;
+    if step == indexes.len() {
+        out.push(chain.clone());
+        return;
+    }
+    for &longer in indexes[step].insertions(chain.last().unwrap()) {
+        if out.len() >= limit {
+            return;
+        }
+        chain.push(longer);
+        extend(indexes, chain, limit, out);
+        chain.pop();
+    }
+}
+
+// Whether exactly one chain follows `spec`.
+pub fn check_unique<'a>(words: &'a [Vec<u8>], spec: &ChainSpec) -> Uniqueness<Vec<&'a [u8]>> {
+    Uniqueness::check(first_chains(words, spec, 2))
+}
+
+// Every way to choose where each step of a chain starting at `start_len` letters inserts its
+// letter, for chains of `steps` steps.
+fn all_positions(start_len: usize, steps: usize) -> Vec<Vec<usize>> {
+    let mut out = vec![Vec::new()];
+    for step in 0..steps {
+        // The longer word has `start_len + step + 1` letters.
+        out = out
+            .into_iter()
+            .flat_map(|positions: Vec<usize>| {
+                (0..=start_len + step).map(move |position| {
+                    let mut positions = positions.clone();
+                    positions.push(position);
+                    positions
+                })
+            })
+            .collect();
+    }
+    out
+}
+
+// New puzzles shaped like `spec`, with the same first word length and number of steps: every
+// choice of where the letters are inserted and of which letter (if any) has to be in every word,
+// such that exactly one chain among `words` fits. Each comes with its chain and the number of
+// partial chains a solver builds on the way there.
+pub fn generate<'a>(
+    words: &'a [Vec<u8>],
+    spec: &ChainSpec,
+) -> Vec<(ChainSpec, Vec<&'a [u8]>, usize)> {
+    let letters: Vec<Option<u8>> = Some(None)
+        .into_iter()
+        .chain((b'a'..=b'z').map(Some))
+        .collect();
+    let mut out = Vec::new();
+    for positions in all_positions(spec.start_len, spec.positions.len()) {
+        for &required_letter in &letters {
+            let spec = ChainSpec {
+                start_len: spec.start_len,
+                positions: positions.clone(),
+                required_letter,
+            };
+            let mut steps = find_chains(words, &spec);
+            let effort = steps.iter().map(|chains| chains.len()).sum();
+            match steps.pop() {
+                Some(mut chains) if chains.len() == 1 => out.push((spec, chains.remove(0), effort)),
+                _ => (),
+            }
+        }
+    }
+    out
+}
+
+// Chains that follow `spec` but for at most `allowed` broken rules: words that aren't in `words`
+// and words without the required letter. Each chain is built around one of `words`: the words
+// before it follow by deleting letters, and the words after it by inserting any letter that
+// `words` use, as long as no more than `allowed` of them are missing. Chains are in dictionary
+// order.
+pub fn near_chains(
+    words: &[Vec<u8>],
+    spec: &ChainSpec,
+    allowed: usize,
+) -> Vec<NearMiss<Vec<Vec<u8>>>> {
+    let known: HashSet<&[u8]> = words.iter().map(|w| w.as_slice()).collect();
+    let letters: BTreeSet<u8> = words.iter().flatten().cloned().collect();
+    let mut chains = BTreeSet::new();
+    for anchor in words {
+        // Where `anchor` would be in the chain.
+        let index = match anchor.len().checked_sub(spec.start_len) {
+            Some(index) if index < spec.chain_len() => index,
+            _ => continue,
+        };
+        let mut chain = vec![anchor.clone()];
+        for (step, &position) in spec.positions[..index].iter().enumerate().rev() {
+            if position > spec.start_len + step {
+                break;
+            }
+            chain.push(delete_at(chain.last().unwrap(), position));
+        }
+        if chain.len() != index + 1 {
+            continue;
+        }
+        chain.reverse();
+        let missing = chain
+            .iter()
+            .filter(|word| !known.contains(word.as_slice()))
+            .count();
+        let near = NearSearch {
+            known: &known,
+            letters: &letters,
+            spec,
+            allowed,
+        };
+        near.finish(&mut chain, missing, &mut chains);
+    }
+    chains
+        .into_iter()
+        .filter_map(|chain| {
+            let mut violations = Vec::new();
+            for word in &chain {
+                let text = String::from_utf8_lossy(word);
+                if !known.contains(word.as_slice()) {
+                    let detail = format!("{} isn't in the dictionary", text);
+                    violations.push(Violation::new(Rule::Word, detail));
+                }
+                if let (false, Some(letter)) = (spec.allows(word), spec.required_letter) {
+                    let detail = format!("{} has no {}", text, letter as char);
+                    violations.push(Violation::new(Rule::Other, detail));
+                }
+            }
+            if violations.len() > allowed {
+                return None;
+            }
+            Some(NearMiss {
+                answer: chain,
+                violations,
+            })
+        })
+        .collect()
+}
+
+struct NearSearch<'a> {
+    known: &'a HashSet<&'a [u8]>,
+    // The letters that can be inserted.
+    letters: &'a BTreeSet<u8>,
+    spec: &'a ChainSpec,
+    allowed: usize,
+}
+
+impl NearSearch<'_> {
+    // Adds every way to finish `chain`, which has `missing` words that aren't known, to `out`.
+    fn finish(&self, chain: &mut Vec<Vec<u8>>, missing: usize, out: &mut BTreeSet<Vec<Vec<u8>>>) {
+        if missing > self.allowed {
+            return;
+        }
+        let step = chain.len() - 1;
+        if step == self.spec.positions.len() {
+            out.insert(chain.clone());
+            return;
+        }
+        let position = self.spec.positions[step];
+        let word = chain.last().unwrap().clone();
+        if position > word.len() {
+            return;
+        }
+        for &letter in self.letters {
+            let mut longer = word.clone();
+            longer.insert(position, letter);
+            let missed = usize::from(!self.known.contains(longer.as_slice()));
+            chain.push(longer);
+            self.finish(chain, missing + missed, out);
+            chain.pop();
+        }
+    }
+}
+
+// The letters in every word of `chain`.
+fn common_letters(chain: &[&[u8]]) -> BTreeSet<u8> {
+    let mut letters: BTreeSet<u8> = chain[0].iter().cloned().collect();
+    for word in &chain[1..] {
+        letters.retain(|letter| word.contains(letter));
+    }
+    letters
+}
+
+// Required letters to use instead of `spec.required_letter` that leave exactly one chain, when
+// `first` and `second` both follow `spec`. Candidates are the letters in every word of one of the
+// chains but not the other.
+pub fn letter_hints(
+    words: &[Vec<u8>],
+    spec: &ChainSpec,
+    first: &[&[u8]],
+    second: &[&[u8]],
+) -> Vec<u8> {
+    let first = common_letters(first);
+    let second = common_letters(second);
+    let candidates = first
+        .symmetric_difference(&second)
+        .cloned()
+        .filter(|&letter| Some(letter) != spec.required_letter);
+    unique_hints(candidates, |&letter| {
+        let spec = ChainSpec {
+            required_letter: Some(letter),
+            ..spec.clone()
+        };
+        check_unique(words, &spec)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{has_insert_five, has_insert_front};
+
+    fn words(list: &[&str]) -> Vec<Vec<u8>> {
+        list.iter().map(|s| s.as_bytes().to_vec()).collect()
+    }
+
+    fn as_strs(chains: &[Vec<&[u8]>]) -> Vec<Vec<String>> {
+        chains
+            .iter()
+            .map(|chain| {
+                chain
+                    .iter()
+                    .map(|w| String::from_utf8(w.to_vec()).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_deletion_index() {
+        let dict = words(&["breach", "preach", "reach", "creaks"]);
+        let index = DeletionIndex::new(dict.iter().map(|w| w.as_slice()), 6, 0);
+        assert_eq!(index.insertions(b"reach").len(), 2);
+        assert!(index.insertions(b"creak").is_empty());
+        let index = DeletionIndex::new(dict.iter().map(|w| w.as_slice()), 6, 5);
+        assert_eq!(index.insertions(b"creak"), &[b"creaks".as_slice()]);
+        assert!(index.insertions(b"reach").is_empty());
+    }
+
+    #[test]
+    fn test_find_chains() {
+        let dict = words(&[
+            "plane", "aplane", "planet", "aplaxne", "aplanle", "blank", "xblank", "xblaqnk",
+        ]);
+        let chains = find_chains(&dict, &ChainSpec::default());
+        assert_eq!(chains.len(), 2);
+        assert_eq!(
+            as_strs(&chains[0]),
+            vec![vec!["plane", "aplane"], vec!["blank", "xblank"]]
+        );
+        assert_eq!(
+            as_strs(&chains[1]),
+            vec![
+                vec!["plane", "aplane", "aplaxne"],
+                vec!["blank", "xblank", "xblaqnk"],
+            ]
+        );
+
+        let no_letter = ChainSpec {
+            required_letter: Some(b'z'),
+            ..Default::default()
+        };
+        assert!(find_chains(&dict, &no_letter)[0].is_empty());
+
+        let append = ChainSpec {
+            start_len: 5,
+            positions: vec![5],
+            required_letter: None,
+        };
+        assert_eq!(
+            as_strs(&find_chains(&dict, &append)[0]),
+            vec![vec!["plane", "planet"]]
+        );
+    }
+
+    #[test]
+    fn test_uniqueness() {
+        let dict = words(&[
+            "plane", "aplane", "planet", "aplaxne", "aplanle", "blank", "xblank", "xblaqnk",
+        ]);
+        let spec = ChainSpec::default();
+        let found = find_chains(&dict, &spec);
+        assert_eq!(first_chains(&dict, &spec, 10), found[1]);
+        assert_eq!(first_chains(&dict, &spec, 1), found[1][..1].to_vec());
+
+        let (first, second) = match check_unique(&dict, &spec) {
+            Uniqueness::Ambiguous(first, second) => (first, second),
+            other => panic!("Expected two chains: {:?}", other),
+        };
+        // 'p' and 'e' are all the way through "plane aplane aplaxne" only, 'b' and 'k' through the
+        // other chain.
+        assert_eq!(
+            letter_hints(&dict, &spec, &first, &second),
+            b"bekp".to_vec()
+        );
+
+        let spec = ChainSpec {
+            required_letter: Some(b'k'),
+            ..spec
+        };
+        assert!(check_unique(&dict, &spec).is_unique());
+        let spec = ChainSpec {
+            required_letter: Some(b'z'),
+            ..spec
+        };
+        assert_eq!(check_unique(&dict, &spec), Uniqueness::None);
+    }
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(
+            all_positions(2, 2),
+            vec![
+                vec![0, 0],
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 0],
+                vec![1, 1],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 0],
+                vec![2, 1],
+                vec![2, 2],
+                vec![2, 3],
+            ]
+        );
+
+        let dict = words(&["at", "bat", "cat", "ate"]);
+        let spec = ChainSpec {
+            start_len: 2,
+            positions: vec![0],
+            required_letter: None,
+        };
+        let found: Vec<_> = generate(&dict, &spec)
+            .into_iter()
+            .map(|(spec, chain, effort)| (spec.positions, spec.required_letter, chain, effort))
+            .collect();
+        // "bat" and "cat" both come from "at", so only "ate" is ever unique.
+        let ate: Vec<&[u8]> = vec![b"at", b"ate"];
+        assert_eq!(
+            found,
+            vec![
+                (vec![2], None, ate.clone(), 1),
+                (vec![2], Some(b'a'), ate.clone(), 1),
+                (vec![2], Some(b't'), ate, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_near_chains() {
+        let details = |misses: Vec<NearMiss<Vec<Vec<u8>>>>| -> Vec<(Vec<Vec<u8>>, Vec<String>)> {
+            misses
+                .into_iter()
+                .map(|miss| {
+                    let details = miss.violations.into_iter().map(|v| v.detail).collect();
+                    (miss.answer, details)
+                })
+                .collect()
+        };
+        // Only the last word is missing, so "pin" can take any letter the dictionary uses.
+        let dict = words(&["in", "pin"]);
+        let spec = ChainSpec {
+            start_len: 2,
+            positions: vec![0, 3],
+            required_letter: Some(b'n'),
+        };
+        let missing = |word: &str| vec![format!("{} isn't in the dictionary", word)];
+        assert_eq!(
+            details(near_chains(&dict, &spec, 1)),
+            vec![
+                (words(&["in", "pin", "pini"]), missing("pini")),
+                (words(&["in", "pin", "pinn"]), missing("pinn")),
+                (words(&["in", "pin", "pinp"]), missing("pinp")),
+            ]
+        );
+        assert!(near_chains(&dict, &spec, 0).is_empty());
+
+        // A missing word in the middle that also lacks the required letter breaks two rules.
+        let dict = words(&["in", "pint"]);
+        let spec = ChainSpec {
+            required_letter: Some(b't'),
+            ..spec
+        };
+        assert!(near_chains(&dict, &spec, 2).is_empty());
+        let found = details(near_chains(&dict, &spec, 3));
+        assert_eq!(
+            found[0],
+            (
+                words(&["in", "pin", "pint"]),
+                vec![
+                    "in has no t".to_string(),
+                    "pin isn't in the dictionary".to_string(),
+                    "pin has no t".to_string(),
+                ]
+            )
+        );
+        // The rest go through "tin" to one of the four words it makes, none of them known.
+        assert_eq!(found.len(), 5);
+    }
+
+    // The indexed search should find exactly what the original quadratic loops found.
+    #[test]
+    fn test_matches_pairwise_search() {
+        let dict = words(&[
+            "lapse", "elapse", "elapsse", "elaspse", "lease", "please", "pleaase", "pleasle",
+            "lines", "clines", "xlines", "clinles", "xlinaes", "fable", "sable", "afable",
+        ]);
+        let spec = ChainSpec::default();
+
+        let with_l: Vec<&Vec<u8>> = dict.iter().filter(|w| w.contains(&b'l')).collect();
+        let mut expected = Vec::new();
+        for five in with_l.iter().filter(|w| w.len() == 5) {
+            for six in with_l.iter().filter(|w| w.len() == 6) {
+                if !has_insert_front(five, six) {
+                    continue;
+                }
+                for seven in with_l.iter().filter(|w| w.len() == 7) {
+                    if has_insert_five(six, seven) {
+                        expected.push(vec![five.as_slice(), six.as_slice(), seven.as_slice()]);
+                    }
+                }
+            }
+        }
+
+        let found = find_chains(&dict, &spec);
+        assert!(!expected.is_empty());
+        assert_eq!(found[1], expected);
+    }
+}
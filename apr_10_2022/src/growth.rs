@@ -0,0 +1,282 @@
+// Growing words one letter at a time: a graph with an edge from every word to each word formed by
+// inserting a single letter anywhere, used to find the longest chains ("i, in, sin, sing, sting,
+// string, staring, starting, startling").
+
+use std::collections::{HashMap, HashSet};
+
+use crate::delete_at;
+
+// Where a letter may be inserted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InsertWhere {
+    Anywhere,
+    Front,
+    Back,
+    // Either the front or the back.
+    Ends,
+    // Anywhere except the front or the back.
+    Interior,
+}
+
+impl InsertWhere {
+    pub fn parse(name: &str) -> Option<InsertWhere> {
+        match name {
+            "any" => Some(InsertWhere::Anywhere),
+            "front" => Some(InsertWhere::Front),
+            "back" => Some(InsertWhere::Back),
+            "ends" => Some(InsertWhere::Ends),
+            "interior" => Some(InsertWhere::Interior),
+            _ => None,
+        }
+    }
+
+    // Whether a letter inserted at `position` of a word that ends up `len` letters long is allowed.
+    fn allows(&self, position: usize, len: usize) -> bool {
+        let front = position == 0;
+        let back = position + 1 == len;
+        match self {
+            InsertWhere::Anywhere => true,
+            InsertWhere::Front => front,
+            InsertWhere::Back => back,
+            InsertWhere::Ends => front || back,
+            InsertWhere::Interior => !front && !back,
+        }
+    }
+}
+
+// Restrictions on each step of a chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InsertRule {
+    // If set, only these letters may be inserted.
+    pub letters: Option<HashSet<u8>>,
+    pub position: InsertWhere,
+}
+
+impl Default for InsertRule {
+    fn default() -> Self {
+        InsertRule {
+            letters: None,
+            position: InsertWhere::Anywhere,
+        }
+    }
+}
+
+impl InsertRule {
+    fn allows(&self, longer: &[u8], position: usize) -> bool {
+        if let Some(letters) = &self.letters {
+            if !letters.contains(&longer[position]) {
+                return false;
+            }
+        }
+        self.position.allows(position, longer.len())
+    }
+}
+
+pub struct InsertionGraph {
+    pub words: Vec<Vec<u8>>,
+    // For each word, the ids of the words formed by inserting one allowed letter.
+    children: Vec<Vec<usize>>,
+}
+
+impl InsertionGraph {
+    // Builds the graph by deleting each letter of every word in turn and looking up what's left,
+    // so construction is linear in the total number of letters.
+    pub fn new(words: &[Vec<u8>], rule: &InsertRule) -> InsertionGraph {
+        let mut unique: Vec<Vec<u8>> = Vec::new();
+        let mut ids: HashMap<&[u8], usize> = HashMap::new();
+        for word in words {
+            if !ids.contains_key(word.as_slice()) {
+                ids.insert(word, unique.len());
+                unique.push(word.clone());
+            }
+        }
+
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); unique.len()];
+        for (id, word) in unique.iter().enumerate() {
+            for position in 0..word.len() {
+                if !rule.allows(word, position) {
+                    continue;
+                }
+                if let Some(&parent) = ids.get(delete_at(word, position).as_slice()) {
+                    // The same child can be reached by inserting a repeated letter in two places.
+                    if !children[parent].contains(&id) {
+                        children[parent].push(id);
+                    }
+                }
+            }
+        }
+
+        InsertionGraph {
+            words: unique,
+            children,
+        }
+    }
+
+    pub fn children(&self, id: usize) -> &[usize] {
+        &self.children[id]
+    }
+
+    // The number of words in the longest chain starting at each word.
+    fn longest_from(&self) -> Vec<usize> {
+        // Every edge goes to a longer word, so longest words first is a topological order.
+        let mut order: Vec<usize> = (0..self.words.len()).collect();
+        order.sort_by_key(|&id| std::cmp::Reverse(self.words[id].len()));
+        let mut longest = vec![1; self.words.len()];
+        for id in order {
+            for &child in &self.children[id] {
+                longest[id] = longest[id].max(longest[child] + 1);
+            }
+        }
+        longest
+    }
+
+    // Finds every chain of maximal length that starts at a word whose length is in `seed_lens`.
+    // Chains are returned as word ids, shortest word first.
+    pub fn longest_chains(&self, seed_lens: &[usize]) -> Vec<Vec<usize>> {
+        let longest = self.longest_from();
+        let seeds: Vec<usize> = (0..self.words.len())
+            .filter(|&id| seed_lens.contains(&self.words[id].len()))
+            .collect();
+        let best = match seeds.iter().map(|&id| longest[id]).max() {
+            Some(best) if best > 1 => best,
+            _ => return Vec::new(),
+        };
+
+        let mut out = Vec::new();
+        let mut chain = Vec::new();
+        for &seed in seeds.iter().filter(|&&id| longest[id] == best) {
+            self.collect_chains(seed, &longest, &mut chain, &mut out);
+        }
+        out
+    }
+
+    fn collect_chains(
+        &self,
+        id: usize,
+        longest: &[usize],
+        chain: &mut Vec<usize>,
+        out: &mut Vec<Vec<usize>>,
+    ) {
+        chain.push(id);
+        if longest[id] == 1 {
+            out.push(chain.clone());
+        } else {
+            for &child in &self.children[id] {
+                if longest[child] + 1 == longest[id] {
+                    self.collect_chains(child, longest, chain, out);
+                }
+            }
+        }
+        chain.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<Vec<u8>> {
+        list.iter().map(|s| s.as_bytes().to_vec()).collect()
+    }
+
+    fn chain_strs(graph: &InsertionGraph, chains: &[Vec<usize>]) -> Vec<String> {
+        chains
+            .iter()
+            .map(|chain| {
+                let chain: Vec<&str> = chain
+                    .iter()
+                    .map(|&id| std::str::from_utf8(&graph.words[id]).unwrap())
+                    .collect();
+                chain.join(" ")
+            })
+            .collect()
+    }
+
+    const STARTLING: &[&str] = &[
+        "i",
+        "in",
+        "sin",
+        "sing",
+        "sting",
+        "string",
+        "staring",
+        "starting",
+        "startling",
+        "an",
+        "tin",
+        "ting",
+        "ant",
+        "pant",
+        "panty",
+        "tint",
+    ];
+
+    #[test]
+    fn test_children() {
+        let graph = InsertionGraph::new(&words(STARTLING), &InsertRule::default());
+        let id = |word: &str| {
+            graph
+                .words
+                .iter()
+                .position(|w| w == word.as_bytes())
+                .unwrap()
+        };
+        let mut children: Vec<usize> = graph.children(id("in")).to_vec();
+        children.sort();
+        assert_eq!(children, vec![id("sin"), id("tin")]);
+        assert_eq!(graph.children(id("ting")), &[id("sting")]);
+    }
+
+    #[test]
+    fn test_longest_chains() {
+        let graph = InsertionGraph::new(&words(STARTLING), &InsertRule::default());
+        assert_eq!(
+            chain_strs(&graph, &graph.longest_chains(&[2])),
+            vec![
+                "in sin sing sting string staring starting startling",
+                "in tin ting sting string staring starting startling",
+            ]
+        );
+        assert_eq!(
+            chain_strs(&graph, &graph.longest_chains(&[3])),
+            vec![
+                "sin sing sting string staring starting startling",
+                "tin ting sting string staring starting startling",
+            ]
+        );
+        assert!(graph.longest_chains(&[10]).is_empty());
+    }
+
+    #[test]
+    fn test_insert_rules() {
+        let front_only = InsertRule {
+            letters: None,
+            position: InsertWhere::Front,
+        };
+        let graph = InsertionGraph::new(&words(STARTLING), &front_only);
+        assert_eq!(
+            chain_strs(&graph, &graph.longest_chains(&[2])),
+            vec!["in sin", "in tin"]
+        );
+
+        let back_only = InsertRule {
+            letters: None,
+            position: InsertWhere::Back,
+        };
+        let graph = InsertionGraph::new(&words(STARTLING), &back_only);
+        assert_eq!(
+            chain_strs(&graph, &graph.longest_chains(&[2])),
+            vec!["an ant"]
+        );
+
+        let only_s = InsertRule {
+            letters: Some([b's'].iter().cloned().collect()),
+            position: InsertWhere::Anywhere,
+        };
+        let graph = InsertionGraph::new(&words(STARTLING), &only_s);
+        assert_eq!(
+            chain_strs(&graph, &graph.longest_chains(&[2])),
+            vec!["in sin"]
+        );
+    }
+}
@@ -0,0 +1,345 @@
+// Word ladders: the shortest ways to get from one word to another where each step is a small edit.
+// This is a Rust take on the Jan 3, 2016 solver, which only allowed inserting or deleting a letter,
+// at least three letters per word and no plurals or verbs formed by adding an 's'.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::delete_at;
+
+// Which edits count as a single step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EdgeTypes {
+    // Insert or delete one letter anywhere ("rose" <-> "rouse").
+    pub insert_delete: bool,
+    // Change one letter ("cold" <-> "cord").
+    pub substitute: bool,
+    // Rearrange all of the letters ("stone" <-> "notes").
+    pub anagram: bool,
+}
+
+impl EdgeTypes {
+    // Parses a comma separated list such as "insert-delete,substitute".
+    pub fn parse(names: &str) -> Option<EdgeTypes> {
+        let mut edges = EdgeTypes {
+            insert_delete: false,
+            substitute: false,
+            anagram: false,
+        };
+        for name in names.split(',') {
+            match name {
+                "insert-delete" => edges.insert_delete = true,
+                "substitute" => edges.substitute = true,
+                "anagram" => edges.anagram = true,
+                _ => return None,
+            }
+        }
+        Some(edges)
+    }
+}
+
+impl Default for EdgeTypes {
+    fn default() -> Self {
+        EdgeTypes {
+            insert_delete: true,
+            substitute: false,
+            anagram: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LadderRules {
+    // Words shorter than this are left out of the graph.
+    pub min_len: usize,
+    // Forbids steps that only add an 's' to the end of a word.
+    pub no_plural_s: bool,
+}
+
+impl Default for LadderRules {
+    // The rules from the Jan 3, 2016 puzzle.
+    fn default() -> Self {
+        LadderRules {
+            min_len: 3,
+            no_plural_s: true,
+        }
+    }
+}
+
+pub struct EditGraph {
+    pub words: Vec<Vec<u8>>,
+    ids: HashMap<Vec<u8>, usize>,
+    neighbors: Vec<Vec<usize>>,
+}
+
+// Adds an undirected edge between `a` and `b`.
+fn connect(neighbors: &mut [HashSet<usize>], a: usize, b: usize) {
+    if a != b {
+        neighbors[a].insert(b);
+        neighbors[b].insert(a);
+    }
+}
+
+// Connects every pair of words that share a key.
+fn connect_groups(neighbors: &mut [HashSet<usize>], groups: HashMap<Vec<u8>, Vec<usize>>) {
+    for group in groups.values() {
+        for (ii, &a) in group.iter().enumerate() {
+            for &b in &group[ii + 1..] {
+                connect(neighbors, a, b);
+            }
+        }
+    }
+}
+
+impl EditGraph {
+    pub fn new(words: &[Vec<u8>], edges: &EdgeTypes, rules: &LadderRules) -> EditGraph {
+        let mut unique: Vec<Vec<u8>> = Vec::new();
+        let mut ids: HashMap<Vec<u8>, usize> = HashMap::new();
+        for word in words {
+            if word.len() >= rules.min_len && !ids.contains_key(word) {
+                ids.insert(word.clone(), unique.len());
+                unique.push(word.clone());
+            }
+        }
+
+        let mut neighbors: Vec<HashSet<usize>> = vec![HashSet::new(); unique.len()];
+        if edges.insert_delete {
+            for (id, word) in unique.iter().enumerate() {
+                for position in 0..word.len() {
+                    let shorter = delete_at(word, position);
+                    // Deleting either 's' of "mass" leaves "mas", so compare the results rather
+                    // than the position.
+                    let plural_s = word.last() == Some(&b's') && shorter == word[..word.len() - 1];
+                    if rules.no_plural_s && plural_s {
+                        continue;
+                    }
+                    if let Some(&shorter) = ids.get(&shorter) {
+                        connect(&mut neighbors, id, shorter);
+                    }
+                }
+            }
+        }
+        if edges.substitute {
+            // Words that match once a position is blanked out differ only at that position.
+            let mut groups: HashMap<Vec<u8>, Vec<usize>> = HashMap::new();
+            for (id, word) in unique.iter().enumerate() {
+                for position in 0..word.len() {
+                    let mut key = word.clone();
+                    key[position] = b'*';
+                    groups.entry(key).or_default().push(id);
+                }
+            }
+            connect_groups(&mut neighbors, groups);
+        }
+        if edges.anagram {
+            let mut groups: HashMap<Vec<u8>, Vec<usize>> = HashMap::new();
+            for (id, word) in unique.iter().enumerate() {
+                let mut key = word.clone();
+                key.sort_unstable();
+                groups.entry(key).or_default().push(id);
+            }
+            connect_groups(&mut neighbors, groups);
+        }
+
+        let neighbors = neighbors
+            .into_iter()
+            .map(|set| {
+                let mut list: Vec<usize> = set.into_iter().collect();
+                list.sort_unstable();
+                list
+            })
+            .collect();
+        EditGraph {
+            words: unique,
+            ids,
+            neighbors,
+        }
+    }
+
+    pub fn id(&self, word: &[u8]) -> Option<usize> {
+        self.ids.get(word).cloned()
+    }
+
+    pub fn neighbors(&self, id: usize) -> &[usize] {
+        &self.neighbors[id]
+    }
+
+    pub fn word(&self, id: usize) -> &str {
+        std::str::from_utf8(&self.words[id]).unwrap()
+    }
+
+    // Finds every shortest path from `from` to `to` with a bidirectional breadth first search.
+    // Each side expands a whole layer at a time, always growing the smaller frontier, and records
+    // every parent in the previous layer so that all of the shortest paths can be rebuilt.
+    pub fn shortest_paths(&self, from: usize, to: usize) -> Vec<Vec<usize>> {
+        if from == to {
+            return vec![vec![from]];
+        }
+
+        let mut forward = Search::new(from);
+        let mut backward = Search::new(to);
+        loop {
+            let (grow, other) = if forward.frontier.len() <= backward.frontier.len() {
+                (&mut forward, &backward)
+            } else {
+                (&mut backward, &forward)
+            };
+            let meeting = grow.expand(self, other);
+            if !meeting.is_empty() {
+                let mut out = Vec::new();
+                for id in meeting {
+                    for head in forward.paths_to(id) {
+                        for tail in backward.paths_to(id) {
+                            let mut path = head.clone();
+                            path.extend(tail.iter().rev().skip(1));
+                            out.push(path);
+                        }
+                    }
+                }
+                out.sort();
+                return out;
+            }
+            if grow.frontier.is_empty() {
+                return Vec::new();
+            }
+        }
+    }
+}
+
+// One side of the bidirectional search.
+struct Search {
+    // Every word reached so far along with all of its parents one layer closer to the start.
+    parents: HashMap<usize, Vec<usize>>,
+    frontier: Vec<usize>,
+}
+
+impl Search {
+    fn new(start: usize) -> Search {
+        let mut parents = HashMap::new();
+        parents.insert(start, Vec::new());
+        Search {
+            parents,
+            frontier: vec![start],
+        }
+    }
+
+    // Expands the frontier by one layer. Returns the newly reached words that the other side's
+    // frontier already contains.
+    fn expand(&mut self, graph: &EditGraph, other: &Search) -> Vec<usize> {
+        let mut next: HashMap<usize, Vec<usize>> = HashMap::new();
+        for &id in &self.frontier {
+            for &neighbor in graph.neighbors(id) {
+                if !self.parents.contains_key(&neighbor) {
+                    next.entry(neighbor).or_default().push(id);
+                }
+            }
+        }
+
+        let mut meeting: Vec<usize> = next
+            .keys()
+            .filter(|id| other.parents.contains_key(id))
+            .cloned()
+            .collect();
+        meeting.sort_unstable();
+        self.frontier = next.keys().cloned().collect();
+        self.frontier.sort_unstable();
+        self.parents.extend(next);
+        meeting
+    }
+
+    // Every path from the start of this search to `id`.
+    fn paths_to(&self, id: usize) -> Vec<Vec<usize>> {
+        let parents = &self.parents[&id];
+        if parents.is_empty() {
+            return vec![vec![id]];
+        }
+        let mut out = Vec::new();
+        for &parent in parents {
+            for mut path in self.paths_to(parent) {
+                path.push(id);
+                out.push(path);
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<Vec<u8>> {
+        list.iter().map(|s| s.as_bytes().to_vec()).collect()
+    }
+
+    fn ladder(graph: &EditGraph, from: &str, to: &str) -> Vec<String> {
+        let from = graph.id(from.as_bytes()).unwrap();
+        let to = graph.id(to.as_bytes()).unwrap();
+        graph
+            .shortest_paths(from, to)
+            .iter()
+            .map(|path| {
+                let path: Vec<&str> = path.iter().map(|&id| graph.word(id)).collect();
+                path.join(" ")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_red_to_rose() {
+        let dict = words(&["red", "rued", "rue", "ruse", "rouse", "rose", "re", "reds"]);
+        let graph = EditGraph::new(&dict, &EdgeTypes::default(), &LadderRules::default());
+        assert_eq!(
+            ladder(&graph, "red", "rose"),
+            vec!["red rued rue ruse rouse rose"]
+        );
+        // "re" is too short and "reds" only adds an 's'.
+        assert!(graph.id(b"re").is_none());
+        assert!(graph.neighbors(graph.id(b"reds").unwrap()).is_empty());
+
+        let loose = LadderRules {
+            min_len: 1,
+            no_plural_s: false,
+        };
+        let graph = EditGraph::new(&dict, &EdgeTypes::default(), &loose);
+        assert_eq!(graph.neighbors(graph.id(b"reds").unwrap()).len(), 1);
+    }
+
+    #[test]
+    fn test_plural_s_double_s() {
+        let dict = words(&["mas", "mass", "mast"]);
+        let graph = EditGraph::new(&dict, &EdgeTypes::default(), &LadderRules::default());
+        // Taking out the first 's' of "mass" only drops an 's' from the end as well.
+        assert!(graph.neighbors(graph.id(b"mass").unwrap()).is_empty());
+        assert_eq!(graph.neighbors(graph.id(b"mast").unwrap()).len(), 1);
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        let dict = words(&[
+            "cold", "cord", "card", "ward", "warm", "word", "worm", "corm",
+        ]);
+        let substitute = EdgeTypes::parse("substitute").unwrap();
+        let graph = EditGraph::new(&dict, &substitute, &LadderRules::default());
+        assert_eq!(
+            ladder(&graph, "cold", "warm"),
+            vec![
+                "cold cord card ward warm",
+                "cold cord word ward warm",
+                "cold cord word worm warm",
+                "cold cord corm worm warm",
+            ]
+        );
+        assert_eq!(ladder(&graph, "cold", "cold"), vec!["cold"]);
+    }
+
+    #[test]
+    fn test_anagram_and_unreachable() {
+        let dict = words(&["stone", "notes", "note", "tone", "zzz"]);
+        let edges = EdgeTypes::parse("insert-delete,anagram").unwrap();
+        let graph = EditGraph::new(&dict, &edges, &LadderRules::default());
+        // "note" -> "notes" only adds an 's', so the path has to go through "stone".
+        assert_eq!(ladder(&graph, "tone", "notes"), vec!["tone stone notes"]);
+        assert!(ladder(&graph, "tone", "zzz").is_empty());
+        assert!(EdgeTypes::parse("teleport").is_none());
+    }
+}
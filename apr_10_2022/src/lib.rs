@@ -0,0 +1,63 @@
+pub mod analytics;
+pub mod chain;
+pub mod growth;
+pub mod ladder;
+
+pub fn has_insert_front(five: &[u8], six: &[u8]) -> bool {
+    assert_eq!(five.len(), 5);
+    assert_eq!(six.len(), 6);
+    five == &six[1..]
+}
+
+pub fn has_insert_five(six: &[u8], seven: &[u8]) -> bool {
+    assert_eq!(six.len(), 6);
+    assert_eq!(seven.len(), 7);
+
+    if six[..4] != seven[..4] {
+        return false;
+    }
+
+    six[4..] == seven[5..]
+}
+
+// Removes the letter at `position` from `word`.
+pub fn delete_at(word: &[u8], position: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(word.len() - 1);
+    out.extend_from_slice(&word[..position]);
+    out.extend_from_slice(&word[position + 1..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_insert_front() {
+        fn help_first(five: &str, six: &str) -> bool {
+            has_insert_front(five.as_bytes(), six.as_bytes())
+        }
+
+        assert!(help_first("reach", "breach"));
+        assert!(help_first("creak", "ccreak"));
+        assert!(!help_first("creak", "creaks"));
+        assert!(!help_first("crept", "accept"));
+    }
+
+    #[test]
+    fn test_has_insert_five() {
+        fn help_five(six: &str, seven: &str) -> bool {
+            has_insert_five(six.as_bytes(), seven.as_bytes())
+        }
+
+        assert!(help_five("abcdef", "abcdgef"));
+        assert!(!help_five("creakk", "creakss"));
+    }
+
+    #[test]
+    fn test_delete_at() {
+        assert_eq!(delete_at(b"breach", 0), b"reach");
+        assert_eq!(delete_at(b"abcdgef", 4), b"abcdef");
+        assert_eq!(delete_at(b"creaks", 5), b"creak");
+    }
+}
@@ -1,105 +1,653 @@
+use std::env;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufWriter, Stdout, Write};
 
-fn has_insert_front(five: &[u8], six: &[u8]) -> bool {
-    assert_eq!(five.len(), 5);
-    assert_eq!(six.len(), 6);
-    five == &six[1..]
-}
+use apr_10_2022::analytics::{components, graph_diameter, hubs, isolated};
+use apr_10_2022::chain::{
+    check_unique, counted_chains, find_chains, generate, letter_hints, near_chains, ChainSpec,
+};
+use apr_10_2022::growth::{InsertRule, InsertWhere, InsertionGraph};
+use apr_10_2022::ladder::{EdgeTypes, EditGraph, LadderRules};
+use word_tools::dictionary::{load_words, read_words, WordFilter, FILTER_USAGE};
+use word_tools::generate::{self, article, GenerateOptions, Proposal, GENERATE_USAGE};
+use word_tools::morphology::Inflection;
+use word_tools::near::{self, NearOptions, NEAR_USAGE};
+use word_tools::solution::{stdout_writer, OutputFormat, Solution, SolutionWriter, OUTPUT_USAGE};
+use word_tools::stats::{Stats, StatsFormat, STATS_USAGE};
+use word_tools::uniqueness::{self, Uniqueness, UNIQUE_USAGE};
 
-fn has_insert_five(six: &[u8], seven: &[u8]) -> bool {
-    assert_eq!(six.len(), 6);
-    assert_eq!(seven.len(), 7);
+const USAGE: &str = "Usage: apr_10_2022 [options]
+  --start-len N         Length of the first word (default 5)
+  --positions P1,P2,..  Zero based position of the inserted letter for each step (default 0,4)
+  --chain-length N      Number of words in the chain; a single position is reused for every step
+  --letter C            Every word must contain C (default l)
+  --any-letter          Don't require any particular letter
 
-    if &six[..4] != &seven[..4] {
-        return false;
-    }
+Longest chains over the whole dictionary, inserting a letter anywhere:
+  --longest             Find every longest chain instead of solving the puzzle
+  --seed-len N1,N2,..   Lengths of the words a chain may start from (default 2,3)
+  --insert-letters ABC  Only these letters may be inserted
+  --insert-where W      any, front, back, ends or interior (default any)
+  --export FILE         Write the chains to FILE instead of stdout
+
+Word ladders and the edit graph:
+  --ladder FROM,TO      Print every shortest ladder from FROM to TO
+  --analyze KIND        components, diameter (the two words farthest apart), hubs or isolated
+  --top N               How many components or hubs to print (default 10)
+  --edges E1,E2,..      insert-delete, substitute and/or anagram (default insert-delete)
+  --min-len N           Skip words shorter than N (default 3)
+  --allow-plural-s      Allow steps that only add an 's' to the end of a word";
 
-    &six[4..] == &seven[5..]
+struct GraphOptions {
+    edges: EdgeTypes,
+    rules: LadderRules,
+    ladder: Option<(String, String)>,
+    analyze: Option<String>,
+    top: usize,
 }
 
-fn main() {
-    let f = match File::open("dictionary.txt") {
-        Ok(f) => f,
-        Err(why) => panic!("Failed to open dictionary.txt: {}", why),
+struct LongestOptions {
+    seed_lens: Vec<usize>,
+    rule: InsertRule,
+    export: Option<String>,
+}
+
+struct Config {
+    spec: ChainSpec,
+    check_unique: bool,
+    generate: GenerateOptions,
+    near: NearOptions,
+    longest: Option<LongestOptions>,
+    graph: Option<GraphOptions>,
+    filter: WordFilter,
+    output: OutputFormat,
+    stats: StatsFormat,
+}
+
+fn parse_args() -> Result<Config, String> {
+    let mut spec = ChainSpec::default();
+    let mut chain_len = None;
+    let mut longest = LongestOptions {
+        seed_lens: vec![2, 3],
+        rule: InsertRule::default(),
+        export: None,
+    };
+    let mut find_longest = false;
+    let mut check_unique = false;
+    let mut generate = GenerateOptions::default();
+    let mut near = NearOptions::default();
+    let mut graph = GraphOptions {
+        edges: EdgeTypes::default(),
+        rules: LadderRules::default(),
+        ladder: None,
+        analyze: None,
+        top: 10,
     };
+    let mut filter = WordFilter::default();
+    let mut output = OutputFormat::default();
+    let mut stats = StatsFormat::default();
 
-    let mut fives = Vec::new();
-    let mut sixes = Vec::new();
-    let mut sevens = Vec::new();
-    for word in BufReader::new(f).lines().map(|s| s.unwrap()) {
-        let word = word.into_bytes();
-        if !word.contains(&b'l') {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if filter.parse_arg(&arg, &mut args)?
+            || output.parse_arg(&arg, &mut args)?
+            || stats.parse_arg(&arg, &mut args)?
+            || generate.parse_arg(&arg, &mut args)?
+            || near.parse_arg(&arg, &mut args)?
+        {
             continue;
         }
-        match word.len() {
-            5 => fives.push(word),
-            6 => sixes.push(word),
-            7 => sevens.push(word),
+        match arg.as_str() {
+            "--any-letter" => {
+                spec.required_letter = None;
+                continue;
+            }
+            "--longest" => {
+                find_longest = true;
+                continue;
+            }
+            "--check-unique" => {
+                check_unique = true;
+                continue;
+            }
+            "--allow-plural-s" => {
+                graph.rules.no_plural_s = false;
+                continue;
+            }
             _ => (),
         }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", arg))?;
+        let parse_number = |value: &str| {
+            value
+                .parse::<usize>()
+                .map_err(|why| format!("Bad value for {}: {}", arg, why))
+        };
+        match arg.as_str() {
+            "--start-len" => spec.start_len = parse_number(&value)?,
+            "--positions" => {
+                spec.positions = value
+                    .split(',')
+                    .map(parse_number)
+                    .collect::<Result<_, _>>()?
+            }
+            "--chain-length" => chain_len = Some(parse_number(&value)?),
+            "--letter" => match value.as_bytes() {
+                [letter] => spec.required_letter = Some(*letter),
+                _ => return Err(format!("Expected a single letter for {}", arg)),
+            },
+            "--seed-len" => {
+                longest.seed_lens = value
+                    .split(',')
+                    .map(parse_number)
+                    .collect::<Result<_, _>>()?
+            }
+            "--insert-letters" => longest.rule.letters = Some(value.bytes().collect()),
+            "--insert-where" => {
+                longest.rule.position = InsertWhere::parse(&value)
+                    .ok_or_else(|| format!("Unknown value for {}: {}", arg, value))?
+            }
+            "--export" => longest.export = Some(value),
+            "--ladder" => match value.split_once(',') {
+                Some((from, to)) => graph.ladder = Some((from.to_string(), to.to_string())),
+                None => return Err(format!("Expected FROM,TO for {}", arg)),
+            },
+            "--analyze" => match value.as_str() {
+                "components" | "diameter" | "hubs" | "isolated" => graph.analyze = Some(value),
+                _ => return Err(format!("Unknown value for {}: {}", arg, value)),
+            },
+            "--top" => graph.top = parse_number(&value)?,
+            "--edges" => {
+                graph.edges = EdgeTypes::parse(&value)
+                    .ok_or_else(|| format!("Unknown value for {}: {}", arg, value))?
+            }
+            "--min-len" => graph.rules.min_len = parse_number(&value)?,
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
     }
 
-    let mut pairs = Vec::new();
-    for five in &fives {
-        for six in &sixes {
-            if has_insert_front(five, six) {
-                pairs.push((five, six));
-            }
+    if let Some(chain_len) = chain_len {
+        if chain_len < 2 {
+            return Err("A chain needs at least two words".to_string());
+        }
+        if spec.positions.len() == 1 {
+            spec.positions = vec![spec.positions[0]; chain_len - 1];
+        } else if spec.chain_len() != chain_len {
+            return Err(format!(
+                "{} positions don't make a chain of {} words",
+                spec.positions.len(),
+                chain_len
+            ));
         }
     }
+    let graph_mode = graph.ladder.is_some() || graph.analyze.is_some();
+    let puzzle_only = check_unique || generate.count.is_some() || near.allowed.is_some();
+    if puzzle_only && (find_longest || graph_mode) {
+        return Err(
+            "--check-unique, --generate and --near only work on the chain puzzle".to_string(),
+        );
+    }
+    // Ladders and analyses have different fields, and a CSV file only has the one header.
+    if output == OutputFormat::Csv && graph.ladder.is_some() && graph.analyze.is_some() {
+        return Err("--output-format csv takes --ladder or --analyze, not both".to_string());
+    }
+    Ok(Config {
+        spec,
+        check_unique,
+        generate,
+        near,
+        longest: if find_longest { Some(longest) } else { None },
+        graph: if graph_mode { Some(graph) } else { None },
+        filter,
+        output,
+        stats,
+    })
+}
 
-    println!("Pairs: {}", pairs.len());
-    /*println!(
-            "Pair 1: {} {}",
-            std::str::from_utf8(&pairs[0].0).unwrap(),
-            std::str::from_utf8(&pairs[0].1).unwrap()
-    );*/
+// Reads the dictionary with `filter` applied.
+fn load(filter: &WordFilter) -> Vec<Vec<u8>> {
+    load_words("dictionary.txt", filter)
+        .into_iter()
+        .map(String::into_bytes)
+        .collect()
+}
 
-    let mut triples = Vec::new();
+// The dictionary filters that aren't on yet and would leave exactly one chain. The dictionary is
+// read once and each filter is applied to a copy of it.
+fn filter_hints(spec: &ChainSpec, filter: &WordFilter) -> Vec<String> {
+    let dictionary = read_words("dictionary.txt");
+    let mut candidates = Vec::new();
+    if !filter.lowercase_only {
+        let lowercase = WordFilter {
+            lowercase_only: true,
+            ..filter.clone()
+        };
+        candidates.push((lowercase, "--lowercase-only".to_string()));
+    }
+    for inflection in Inflection::all() {
+        if !filter.reject.contains(&inflection) {
+            let mut reject = filter.clone();
+            reject.reject.push(inflection);
+            candidates.push((reject, format!("--reject {}", inflection.name())));
+        }
+    }
+    candidates
+        .into_iter()
+        .filter(|(filter, _)| {
+            let words: Vec<Vec<u8>> = filter
+                .apply(dictionary.clone())
+                .into_iter()
+                .map(String::into_bytes)
+                .collect();
+            check_unique(&words, spec).is_unique()
+        })
+        .map(|(_, option)| option)
+        .collect()
+}
 
-    for pair in &pairs {
-        for seven in &sevens {
-            if has_insert_five(pair.1, seven) {
-                triples.push((pair.0, pair.1, seven));
-            }
+// Reports whether the chain puzzle has exactly one answer, and if it has more, the options that
+// would make it unique.
+fn check_chain(words: &[Vec<u8>], config: &Config) {
+    let found = check_unique(words, &config.spec);
+    let mut suggestions = Vec::new();
+    if let Uniqueness::Ambiguous(first, second) = &found {
+        for letter in letter_hints(words, &config.spec, first, second) {
+            suggestions.push(format!("--letter {}", letter as char));
         }
+        suggestions.extend(filter_hints(&config.spec, &config.filter));
+    }
+    let describe = |chain: &Vec<&[u8]>| {
+        let chain: Vec<&str> = chain
+            .iter()
+            .map(|word| std::str::from_utf8(word).unwrap())
+            .collect();
+        chain.join(" ")
+    };
+    uniqueness::report(&found, describe, &suggestions, config.output).unwrap();
+}
+
+// "first", "second" and so on, as in "insert a letter as the second letter".
+fn ordinal(n: usize) -> String {
+    const NAMES: [&str; 10] = [
+        "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth",
+        "tenth",
+    ];
+    match NAMES.get(n.wrapping_sub(1)) {
+        Some(name) => name.to_string(),
+        None => format!("{}th", n),
     }
+}
 
-    println!("Triples: {}", triples.len());
-    for triple in &triples {
-        println!(
-            "{} {} {}",
-            std::str::from_utf8(triple.0).unwrap(),
-            std::str::from_utf8(triple.1).unwrap(),
-            std::str::from_utf8(triple.2).unwrap()
+// The puzzle as it would be read out, e.g. "Think of a 5-letter word. Insert a letter at the
+// front to get a 6-letter word, then insert a letter as the fifth letter to get a 7-letter word.
+// Every word contains an L."
+fn statement(spec: &ChainSpec) -> String {
+    let mut out = format!(
+        "Think of {} {}-letter word.",
+        article(spec.start_len),
+        spec.start_len
+    );
+    for (step, &position) in spec.positions.iter().enumerate() {
+        let len = spec.start_len + step + 1;
+        let place = if position == 0 {
+            "at the front".to_string()
+        } else if position + 1 == len {
+            "at the end".to_string()
+        } else {
+            format!("as the {} letter", ordinal(position + 1))
+        };
+        let lead = if step == 0 {
+            " Insert"
+        } else {
+            ", then insert"
+        };
+        out += &format!(
+            "{} a letter {} to get {} {}-letter word",
+            lead,
+            place,
+            article(len),
+            len
         );
     }
+    out.push('.');
+    if let Some(letter) = spec.required_letter {
+        let letter = (letter as char).to_ascii_uppercase();
+        let article = if "AEFHILMNORSX".contains(letter) {
+            "an"
+        } else {
+            "a"
+        };
+        out += &format!(" Every word contains {} {}.", article, letter);
+    }
+    out
+}
+
+// Proposes up to `count` puzzles shaped like `config.spec` (same first word length and number of
+// steps) that have exactly one answer among the common words.
+fn generate_puzzles(config: &Config, count: usize) -> Result<(), String> {
+    let common = config.generate.load()?;
+    let words: Vec<Vec<u8>> = config
+        .filter
+        .apply(common.words().to_vec())
+        .into_iter()
+        .map(String::into_bytes)
+        .collect();
+    let proposals = generate(&words, &config.spec)
+        .into_iter()
+        .map(|(spec, chain, effort)| {
+            let chain: Vec<&str> = chain
+                .iter()
+                .map(|word| std::str::from_utf8(word).unwrap())
+                .collect();
+            Proposal {
+                statement: statement(&spec),
+                answer: chain.join(" "),
+                rarest: common.rarest(chain.iter().cloned()),
+                effort,
+            }
+        })
+        .collect();
+    generate::report(proposals, count, config.output).map_err(|why| why.to_string())
+}
+
+// A chain of words as a solution.
+fn chain_solution(chain: &[&str]) -> Solution {
+    Solution::new()
+        .number("length", chain.len())
+        .list("chain", chain)
+}
+
+fn print_longest(
+    words: &[Vec<u8>],
+    options: &LongestOptions,
+    output: OutputFormat,
+    stats: &mut Stats,
+) {
+    let graph = InsertionGraph::new(words, &options.rule);
+    stats.record("insertion graph", words.len(), graph.words.len());
+    let chains = graph.longest_chains(&options.seed_lens);
+    stats.record("longest chains", graph.words.len(), chains.len());
+    if output == OutputFormat::Text {
+        match chains.first() {
+            Some(chain) => println!("Longest chains: {} of {} words", chains.len(), chain.len()),
+            None => println!("Longest chains: 0"),
+        }
+    }
+
+    let mut out: Box<dyn Write> = match &options.export {
+        Some(path) => match File::create(path) {
+            Ok(f) => Box::new(BufWriter::new(f)),
+            Err(why) => {
+                eprintln!("Failed to create {}: {}", path, why);
+                std::process::exit(1);
+            }
+        },
+        None => Box::new(std::io::stdout()),
+    };
+    let chains = chains.iter().map(|chain| {
+        chain
+            .iter()
+            .map(|&id| std::str::from_utf8(&graph.words[id]).unwrap())
+            .collect::<Vec<&str>>()
+    });
+    if output == OutputFormat::Text {
+        for chain in chains {
+            writeln!(out, "{}", chain.join(" ")).unwrap();
+        }
+    } else {
+        let mut writer = SolutionWriter::new(output, out);
+        for chain in chains {
+            writer.write(&chain_solution(&chain)).unwrap();
+        }
+    }
+}
+
+fn print_path(graph: &EditGraph, path: &[usize]) {
+    let path: Vec<&str> = path.iter().map(|&id| graph.word(id)).collect();
+    println!("{}", path.join(" -> "));
+}
+
+// The words along `path` as a solution.
+fn path_solution(graph: &EditGraph, path: &[usize]) -> Solution {
+    let words: Vec<&str> = path.iter().map(|&id| graph.word(id)).collect();
+    Solution::new()
+        .text("from", words[0])
+        .text("to", words[words.len() - 1])
+        .number("steps", path.len() - 1)
+        .list("path", words)
+}
+
+// Prints every shortest ladder from `from` to `to`. Returns how many there are.
+fn print_ladders(
+    graph: &EditGraph,
+    from: &str,
+    to: &str,
+    writer: &mut Option<SolutionWriter<Stdout>>,
+) -> usize {
+    let id = |word: &str| match graph.id(word.as_bytes()) {
+        Some(id) => id,
+        None => {
+            eprintln!("{} isn't in the dictionary", word);
+            std::process::exit(1);
+        }
+    };
+    let paths = graph.shortest_paths(id(from), id(to));
+    let found = paths.len();
+    if let Some(writer) = writer {
+        for path in paths {
+            writer.write(&path_solution(graph, &path)).unwrap();
+        }
+        return found;
+    }
+    match paths.first() {
+        Some(path) => println!(
+            "Shortest ladders: {} of {} steps",
+            paths.len(),
+            path.len() - 1
+        ),
+        None => println!("No ladder from {} to {}", from, to),
+    }
+    for path in paths {
+        print_path(graph, &path);
+    }
+    found
+}
+
+// Writes the results of `print_analysis` as solutions. Returns how many there are.
+fn write_analysis(
+    graph: &EditGraph,
+    kind: &str,
+    top: usize,
+    writer: &mut SolutionWriter<Stdout>,
+) -> usize {
+    let solutions: Vec<Solution> = match kind {
+        "components" => components(graph)
+            .iter()
+            .take(top)
+            .map(|component| {
+                Solution::new()
+                    .number("size", component.len())
+                    .list("words", component.iter().map(|&id| graph.word(id)))
+            })
+            .collect(),
+        "diameter" => graph_diameter(graph)
+            .map(|(from, to, _)| path_solution(graph, &graph.shortest_paths(from, to)[0]))
+            .into_iter()
+            .collect(),
+        "hubs" => hubs(graph, top)
+            .into_iter()
+            .map(|(id, degree)| {
+                Solution::new()
+                    .text("word", graph.word(id))
+                    .number("degree", degree)
+            })
+            .collect(),
+        "isolated" => isolated(graph)
+            .into_iter()
+            .map(|id| Solution::new().text("word", graph.word(id)))
+            .collect(),
+        _ => unreachable!(),
+    };
+    for solution in &solutions {
+        writer.write(solution).unwrap();
+    }
+    solutions.len()
+}
+
+// Prints the `kind` analysis of `graph`. Returns how many components, paths or words it lists.
+fn print_analysis(graph: &EditGraph, kind: &str, top: usize) -> usize {
+    match kind {
+        "components" => {
+            let found = components(graph);
+            println!("Components: {}", found.len());
+            for component in found.iter().take(top) {
+                let sample: Vec<&str> =
+                    component.iter().take(5).map(|&id| graph.word(id)).collect();
+                let more = if component.len() > sample.len() {
+                    " ..."
+                } else {
+                    ""
+                };
+                println!("{} words: {}{}", component.len(), sample.join(" "), more);
+            }
+            found.len().min(top)
+        }
+        "diameter" => match graph_diameter(graph) {
+            Some((from, to, distance)) => {
+                println!(
+                    "Diameter {}: {} to {}",
+                    distance,
+                    graph.word(from),
+                    graph.word(to)
+                );
+                print_path(graph, &graph.shortest_paths(from, to)[0]);
+                1
+            }
+            None => {
+                println!("No two words are connected");
+                0
+            }
+        },
+        "hubs" => {
+            let found = hubs(graph, top);
+            for &(id, degree) in &found {
+                println!("{} {}", graph.word(id), degree);
+            }
+            found.len()
+        }
+        "isolated" => {
+            let found = isolated(graph);
+            println!("Isolated words: {}", found.len());
+            for &id in &found {
+                println!("{}", graph.word(id));
+            }
+            found.len()
+        }
+        _ => unreachable!(),
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn main() {
+    let config = match parse_args() {
+        Ok(config) => config,
+        Err(why) => {
+            eprintln!(
+                "{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}",
+                why,
+                USAGE,
+                UNIQUE_USAGE,
+                GENERATE_USAGE,
+                NEAR_USAGE,
+                FILTER_USAGE,
+                OUTPUT_USAGE,
+                STATS_USAGE
+            );
+            std::process::exit(1);
+        }
+    };
 
-    #[test]
-    fn test_has_insert_front() {
-        fn help_first(five: &str, six: &str) -> bool {
-            has_insert_front(five.as_bytes(), six.as_bytes())
+    if let Some(count) = config.generate.count {
+        if let Err(why) = generate_puzzles(&config, count) {
+            eprintln!("{}", why);
+            std::process::exit(1);
         }
+        return;
+    }
+    let mut stats = Stats::new();
+    let words = load(&config.filter);
+    stats.record("dictionary", words.len(), words.len());
+    if config.check_unique {
+        check_chain(&words, &config);
+        return;
+    }
+    if let Some(allowed) = config.near.allowed {
+        let misses = near_chains(&words, &config.spec, allowed);
+        let describe = |chain: &Vec<Vec<u8>>| {
+            let chain: Vec<String> = chain
+                .iter()
+                .map(|word| String::from_utf8_lossy(word).into_owned())
+                .collect();
+            chain.join(" ")
+        };
+        near::report(misses, describe, config.near.limit, config.output).unwrap();
+        return;
+    }
 
-        assert!(help_first("reach", "breach"));
-        assert!(help_first("creak", "ccreak"));
-        assert!(!help_first("creak", "creaks"));
-        assert!(!help_first("crept", "accept"));
+    let mut writer = stdout_writer(config.output);
+    if let Some(options) = &config.graph {
+        let graph = EditGraph::new(&words, &options.edges, &options.rules);
+        stats.record("edit graph", words.len(), graph.words.len());
+        if let Some((from, to)) = &options.ladder {
+            let found = print_ladders(&graph, from, to, &mut writer);
+            stats.record("ladders", graph.words.len(), found);
+        }
+        if let Some(kind) = &options.analyze {
+            let found = match &mut writer {
+                Some(writer) => write_analysis(&graph, kind, options.top, writer),
+                None => print_analysis(&graph, kind, options.top),
+            };
+            stats.record(kind, graph.words.len(), found);
+        }
+        stats.report(config.stats);
+        return;
+    }
+    if let Some(options) = &config.longest {
+        print_longest(&words, options, config.output, &mut stats);
+        stats.report(config.stats);
+        return;
     }
 
-    #[test]
-    fn test_has_insert_five() {
-        fn help_five(six: &str, seven: &str) -> bool {
-            has_insert_five(six.as_bytes(), seven.as_bytes())
+    let steps = match config.stats {
+        StatsFormat::Off => find_chains(&words, &config.spec),
+        _ => counted_chains(&words, &config.spec, &mut stats),
+    };
+    stats.report(config.stats);
+    if let Some(writer) = &mut writer {
+        for chain in steps.last().into_iter().flatten() {
+            let chain: Vec<&str> = chain
+                .iter()
+                .map(|word| std::str::from_utf8(word).unwrap())
+                .collect();
+            writer.write(&chain_solution(&chain)).unwrap();
+        }
+        return;
+    }
+    for (step, chains) in steps.iter().enumerate() {
+        match step + 2 {
+            2 => println!("Pairs: {}", chains.len()),
+            3 => println!("Triples: {}", chains.len()),
+            n => println!("Chains of {}: {}", n, chains.len()),
         }
+    }
 
-        assert!(help_five("abcdef", "abcdgef"));
-        assert!(!help_five("creakk", "creakss"));
+    if let Some(chains) = steps.last() {
+        for chain in chains {
+            let chain: Vec<&str> = chain
+                .iter()
+                .map(|word| std::str::from_utf8(word).unwrap())
+                .collect();
+            println!("{}", chain.join(" "));
+        }
     }
 }
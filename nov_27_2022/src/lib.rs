@@ -0,0 +1,735 @@
+/*
+  Helpers for the NPR puzzle presented on Nov 27, 2022.
+
+  A (paraphrased) statement of the problem:
+    Find an eight letter word where removing the fourth letter leaves a three letter word followed
+    by a four letter word.
+*/
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+
+use regex::Regex;
+use word_tools::near::{NearMiss, Rule, Violation};
+use word_tools::solution::Solution;
+use word_tools::stats::Stats;
+use word_tools::uniqueness::{unique_hints, Uniqueness};
+
+// Describes where a word is cut: `prefix_len` letters, then `removed_len` letters that are dropped,
+// then `suffix_len` letters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SplitSpec {
+    pub prefix_len: usize,
+    pub removed_len: usize,
+    pub suffix_len: usize,
+}
+
+impl SplitSpec {
+    pub fn word_len(&self) -> usize {
+        self.prefix_len + self.removed_len + self.suffix_len
+    }
+}
+
+impl fmt::Display for SplitSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}",
+            self.prefix_len, self.removed_len, self.suffix_len
+        )
+    }
+}
+
+impl Default for SplitSpec {
+    // The original puzzle: 3 letters, drop 1, 4 letters.
+    fn default() -> Self {
+        SplitSpec {
+            prefix_len: 3,
+            removed_len: 1,
+            suffix_len: 4,
+        }
+    }
+}
+
+// A word along with the two parts that remain and the letter(s) that were removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Split {
+    pub word: String,
+    pub prefix: String,
+    pub removed: String,
+    pub suffix: String,
+}
+
+impl Split {
+    // Cuts `word` according to `spec`. Returns None if the word is the wrong length or isn't
+    // plain ASCII (so that byte offsets line up with letters).
+    pub fn new(word: &str, spec: &SplitSpec) -> Option<Split> {
+        if word.len() != spec.word_len() || !word.is_ascii() {
+            return None;
+        }
+        let suffix_start = spec.prefix_len + spec.removed_len;
+        Some(Split {
+            word: word.to_owned(),
+            prefix: word[..spec.prefix_len].to_owned(),
+            removed: word[spec.prefix_len..suffix_start].to_owned(),
+            suffix: word[suffix_start..].to_owned(),
+        })
+    }
+
+    pub fn to_solution(&self) -> Solution {
+        Solution::new()
+            .text("word", &self.word)
+            .text("prefix", &self.prefix)
+            .text("removed", &self.removed)
+            .text("suffix", &self.suffix)
+    }
+}
+
+// Extra requirements on one of the two remaining parts. Every part must already be in the
+// dictionary; these narrow things down further (e.g. "both parts are animals").
+#[derive(Clone, Debug, Default)]
+pub struct PartConstraint {
+    pub word_list: Option<HashSet<String>>,
+    pub pattern: Option<Regex>,
+}
+
+impl PartConstraint {
+    pub fn matches(&self, part: &str) -> bool {
+        if let Some(list) = &self.word_list {
+            if !list.contains(part) {
+                return false;
+            }
+        }
+        if let Some(pattern) = &self.pattern {
+            if !pattern.is_match(part) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Constraints {
+    pub prefix: PartConstraint,
+    pub suffix: PartConstraint,
+    // If set, every removed letter must be one of these.
+    pub removed_letters: Option<HashSet<char>>,
+}
+
+impl Constraints {
+    pub fn matches(&self, split: &Split) -> bool {
+        if let Some(letters) = &self.removed_letters {
+            if !split.removed.chars().all(|ch| letters.contains(&ch)) {
+                return false;
+            }
+        }
+        self.prefix.matches(&split.prefix) && self.suffix.matches(&split.suffix)
+    }
+}
+
+// The dictionary words the right length to be the first and second parts of a split.
+struct Parts<'a> {
+    prefixes: HashSet<&'a str>,
+    suffixes: HashSet<&'a str>,
+}
+
+impl<'a> Parts<'a> {
+    fn new(words: &'a [String], spec: &SplitSpec) -> Parts<'a> {
+        let of_len = |len: usize| {
+            words
+                .iter()
+                .filter(|w| w.len() == len)
+                .map(|w| w.as_str())
+                .collect()
+        };
+        Parts {
+            prefixes: of_len(spec.prefix_len),
+            suffixes: of_len(spec.suffix_len),
+        }
+    }
+
+    fn has_prefix(&self, split: &Split) -> bool {
+        self.prefixes.contains(split.prefix.as_str())
+    }
+
+    fn has_suffix(&self, split: &Split) -> bool {
+        self.suffixes.contains(split.suffix.as_str())
+    }
+}
+
+// Finds every word in `words` that splits according to `spec` into two dictionary words and
+// satisfies `constraints`.
+pub fn find_splits(words: &[String], spec: &SplitSpec, constraints: &Constraints) -> Vec<Split> {
+    splits(words, spec, constraints).collect()
+}
+
+// Like `find_splits`, but lazily so that the caller can stop early.
+pub fn splits<'a>(
+    words: &'a [String],
+    spec: &'a SplitSpec,
+    constraints: &'a Constraints,
+) -> impl Iterator<Item = Split> + 'a {
+    let parts = Parts::new(words, spec);
+    words
+        .iter()
+        .filter_map(|word| Split::new(word, spec))
+        .filter(move |split| parts.has_prefix(split) && parts.has_suffix(split))
+        .filter(|split| constraints.matches(split))
+}
+
+// `find_splits`, recording how many splits each check keeps in `stats`. Every cut is collected
+// before it's checked so that each check can be counted, so only use this for --stats.
+pub fn counted_splits(
+    words: &[String],
+    spec: &SplitSpec,
+    constraints: &Constraints,
+    stats: &mut Stats,
+) -> Vec<Split> {
+    let parts = Parts::new(words, spec);
+    let mut splits: Vec<Split> = words
+        .iter()
+        .filter_map(|word| Split::new(word, spec))
+        .collect();
+    stats.record("cut", words.len(), splits.len());
+    let before = splits.len();
+    splits.retain(|split| parts.has_prefix(split));
+    stats.record("prefix is a word", before, splits.len());
+    let before = splits.len();
+    splits.retain(|split| parts.has_suffix(split));
+    stats.record("suffix is a word", before, splits.len());
+    let before = splits.len();
+    splits.retain(|split| constraints.matches(split));
+    stats.record("constraints", before, splits.len());
+    splits
+}
+
+// Whether exactly one word splits according to `spec` and satisfies `constraints`.
+pub fn check_unique(
+    words: &[String],
+    spec: &SplitSpec,
+    constraints: &Constraints,
+) -> Uniqueness<Split> {
+    Uniqueness::check(splits(words, spec, constraints))
+}
+
+// An extra constraint a puzzle setter could add to rule out other answers.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Hint {
+    // The letter(s) that may be removed, sorted, as `--removed` takes them.
+    Removed(String),
+    // The first letter of the first part.
+    PrefixStart(char),
+    // The last letter of the second part.
+    SuffixEnd(char),
+}
+
+impl Hint {
+    pub fn apply(&self, constraints: &mut Constraints) {
+        let pattern = |pattern: String| Some(Regex::new(&pattern).unwrap());
+        match self {
+            Hint::Removed(letters) => constraints.removed_letters = Some(letters.chars().collect()),
+            Hint::PrefixStart(letter) => {
+                constraints.prefix.pattern =
+                    pattern(format!("^{}", regex::escape(&letter.to_string())))
+            }
+            Hint::SuffixEnd(letter) => {
+                constraints.suffix.pattern =
+                    pattern(format!("{}$", regex::escape(&letter.to_string())))
+            }
+        }
+    }
+}
+
+// The letters removed from `split` as a hint.
+fn removed_hint(split: &Split) -> Hint {
+    let letters: BTreeSet<char> = split.removed.chars().collect();
+    Hint::Removed(letters.into_iter().collect())
+}
+
+// The hints `split` satisfies.
+fn hints_for(split: &Split) -> Vec<Hint> {
+    let mut hints = vec![removed_hint(split)];
+    hints.extend(split.prefix.chars().next().map(Hint::PrefixStart));
+    hints.extend(split.suffix.chars().last().map(Hint::SuffixEnd));
+    hints
+}
+
+// New puzzles: `spec` plus one hint, such that exactly one word in `words` splits that way. Each
+// comes with its answer and how many words of the right length fit the hint, which is how many a
+// solver has to check. Sorted by hint.
+pub fn generate(words: &[String], spec: &SplitSpec) -> Vec<(Hint, Split, usize)> {
+    let splits = find_splits(words, spec, &Constraints::default());
+    let hints: BTreeSet<Hint> = splits.iter().flat_map(hints_for).collect();
+    // A hint can fit splits it didn't come from: removing "ab" also allows "ba" and "aa".
+    hints
+        .into_iter()
+        .filter_map(|hint| {
+            let mut constraints = Constraints::default();
+            hint.apply(&mut constraints);
+            let answer = match Uniqueness::check(splits.iter().filter(|s| constraints.matches(s))) {
+                Uniqueness::Unique(split) => split.clone(),
+                _ => return None,
+            };
+            let effort = words
+                .iter()
+                .filter_map(|word| Split::new(word, spec))
+                .filter(|split| constraints.matches(split))
+                .count();
+            Some((hint, answer, effort))
+        })
+        .collect()
+}
+
+// The hints that each leave exactly one answer, when `first` and `second` both satisfy
+// `constraints`. Candidates come from where the two differ, for the parts of `constraints` that
+// aren't already set.
+pub fn suggest(
+    words: &[String],
+    spec: &SplitSpec,
+    constraints: &Constraints,
+    first: &Split,
+    second: &Split,
+) -> Vec<Hint> {
+    let mut hints = Vec::new();
+    for (split, other) in [(first, second), (second, first)] {
+        let removed = removed_hint(split);
+        if constraints.removed_letters.is_none() && removed != removed_hint(other) {
+            hints.push(removed);
+        }
+        let start = split.prefix.chars().next();
+        if constraints.prefix.pattern.is_none() && start != other.prefix.chars().next() {
+            hints.extend(start.map(Hint::PrefixStart));
+        }
+        let end = split.suffix.chars().last();
+        if constraints.suffix.pattern.is_none() && end != other.suffix.chars().last() {
+            hints.extend(end.map(Hint::SuffixEnd));
+        }
+    }
+    unique_hints(hints, |hint| {
+        let mut constraints = constraints.clone();
+        hint.apply(&mut constraints);
+        check_unique(words, spec, &constraints)
+    })
+}
+
+// What `part`, the `which` part of a split, gets wrong: not being a word, not being in
+// `constraint`'s word list and not matching its pattern.
+fn part_violations(
+    part: &str,
+    which: &str,
+    known: &HashSet<&str>,
+    constraint: &PartConstraint,
+) -> Vec<Violation> {
+    let mut out = Vec::new();
+    if !known.contains(part) {
+        out.push(Violation::new(Rule::Word, format!("{} isn't a word", part)));
+    }
+    if let Some(list) = &constraint.word_list {
+        if !list.contains(part) {
+            let detail = format!("{} isn't in the {} part's list", part, which);
+            out.push(Violation::new(Rule::Other, detail));
+        }
+    }
+    if let Some(pattern) = &constraint.pattern {
+        if !pattern.is_match(part) {
+            let detail = format!("{} doesn't match {}", part, pattern);
+            out.push(Violation::new(Rule::Other, detail));
+        }
+    }
+    out
+}
+
+// Words that split according to `spec` but for at most `allowed` broken rules: parts that aren't
+// words and `constraints` they don't meet. In the order of `words`.
+pub fn near_splits(
+    words: &[String],
+    spec: &SplitSpec,
+    constraints: &Constraints,
+    allowed: usize,
+) -> Vec<NearMiss<Split>> {
+    let known: HashSet<&str> = words.iter().map(|w| w.as_str()).collect();
+    words
+        .iter()
+        .filter_map(|word| Split::new(word, spec))
+        .filter_map(|split| {
+            let mut violations =
+                part_violations(&split.prefix, "first", &known, &constraints.prefix);
+            if let Some(letters) = &constraints.removed_letters {
+                if !split.removed.chars().all(|ch| letters.contains(&ch)) {
+                    let detail = format!("{} can't be removed", split.removed);
+                    violations.push(Violation::new(Rule::Other, detail));
+                }
+            }
+            violations.extend(part_violations(
+                &split.suffix,
+                "second",
+                &known,
+                &constraints.suffix,
+            ));
+            if violations.len() > allowed {
+                return None;
+            }
+            Some(NearMiss {
+                answer: split,
+                violations,
+            })
+        })
+        .collect()
+}
+
+// Limits for `sweep_splits`. Words outside `min_word_len..=max_word_len` are skipped, as are cuts
+// that would leave a part shorter than `min_part_len`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SweepLimits {
+    pub min_word_len: usize,
+    pub max_word_len: usize,
+    pub min_part_len: usize,
+    pub removed_len: usize,
+}
+
+impl Default for SweepLimits {
+    fn default() -> Self {
+        SweepLimits {
+            min_word_len: 1,
+            max_word_len: usize::MAX,
+            min_part_len: 1,
+            removed_len: 1,
+        }
+    }
+}
+
+// Tries every cut of every word in one pass: each word is checked at every position against a
+// single index of the whole dictionary, so all word lengths and positions cost one hash lookup
+// per part. The results are keyed by the cut pattern (e.g. 3|1|4).
+pub fn sweep_splits(
+    words: &[String],
+    limits: &SweepLimits,
+    constraints: &Constraints,
+) -> BTreeMap<SplitSpec, Vec<Split>> {
+    let index: HashSet<&str> = words.iter().map(|w| w.as_str()).collect();
+
+    let mut out = BTreeMap::new();
+    for word in words {
+        let len = word.len();
+        if len < limits.min_word_len || len > limits.max_word_len || !word.is_ascii() {
+            continue;
+        }
+        if len < limits.removed_len + 2 * limits.min_part_len {
+            continue;
+        }
+        let last_prefix_len = len - limits.removed_len - limits.min_part_len;
+        for prefix_len in limits.min_part_len..=last_prefix_len {
+            let spec = SplitSpec {
+                prefix_len,
+                removed_len: limits.removed_len,
+                suffix_len: len - limits.removed_len - prefix_len,
+            };
+            let split = match Split::new(word, &spec) {
+                Some(split) => split,
+                None => continue,
+            };
+            if !index.contains(split.prefix.as_str()) || !index.contains(split.suffix.as_str()) {
+                continue;
+            }
+            if !constraints.matches(&split) {
+                continue;
+            }
+            out.entry(spec).or_insert_with(Vec::new).push(split);
+        }
+    }
+    out
+}
+
+// Groups the splits by the letter(s) that were removed.
+pub fn group_by_removed(splits: Vec<Split>) -> BTreeMap<String, Vec<Split>> {
+    let mut groups = BTreeMap::new();
+    for split in splits {
+        groups
+            .entry(split.removed.clone())
+            .or_insert_with(Vec::new)
+            .push(split);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_split_new() {
+        let split = Split::new("catsdogs", &SplitSpec::default()).unwrap();
+        assert_eq!(split.prefix, "cat");
+        assert_eq!(split.removed, "s");
+        assert_eq!(split.suffix, "dogs");
+        assert_eq!(
+            split.to_solution().to_json(),
+            r#"{"word":"catsdogs","prefix":"cat","removed":"s","suffix":"dogs"}"#
+        );
+
+        assert_eq!(Split::new("cats", &SplitSpec::default()), None);
+    }
+
+    #[test]
+    fn test_find_splits() {
+        let dict = words(&[
+            "cat", "dogs", "catsdogs", "catadogs", "ratxdogs", "cow", "cowedogs",
+        ]);
+        let found = find_splits(&dict, &SplitSpec::default(), &Constraints::default());
+        let found: Vec<&str> = found.iter().map(|s| s.word.as_str()).collect();
+        assert_eq!(found, vec!["catsdogs", "catadogs", "cowedogs"]);
+
+        // Counting each check finds the same splits.
+        let mut stats = Stats::new();
+        let counted = counted_splits(
+            &dict,
+            &SplitSpec::default(),
+            &Constraints::default(),
+            &mut stats,
+        );
+        let counts: Vec<(usize, usize)> =
+            stats.stages.iter().map(|s| (s.input, s.output)).collect();
+        assert_eq!(counts, vec![(7, 4), (4, 3), (3, 3), (3, 3)]);
+        assert_eq!(counted.len(), 3);
+    }
+
+    #[test]
+    fn test_constraints() {
+        let dict = words(&["cat", "dogs", "catsdogs", "catadogs", "cow", "cowedogs"]);
+        let vowels = Constraints {
+            removed_letters: Some("aeiou".chars().collect()),
+            ..Default::default()
+        };
+        let found = find_splits(&dict, &SplitSpec::default(), &vowels);
+        assert_eq!(found.len(), 2);
+
+        let cats_only = Constraints {
+            prefix: PartConstraint {
+                word_list: Some(["cat".to_string()].iter().cloned().collect()),
+                pattern: None,
+            },
+            ..Default::default()
+        };
+        let found = find_splits(&dict, &SplitSpec::default(), &cats_only);
+        assert_eq!(found.len(), 2);
+
+        let ends_in_s = Constraints {
+            suffix: PartConstraint {
+                word_list: None,
+                pattern: Some(Regex::new("^d.*s$").unwrap()),
+            },
+            prefix: PartConstraint {
+                word_list: None,
+                pattern: Some(Regex::new("^co").unwrap()),
+            },
+            ..Default::default()
+        };
+        let found = find_splits(&dict, &SplitSpec::default(), &ends_in_s);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].word, "cowedogs");
+    }
+
+    #[test]
+    fn test_uniqueness() {
+        let dict = words(&["cat", "dogs", "catsdogs", "catadogs", "cow", "cowedogs"]);
+        let spec = SplitSpec::default();
+        let found = check_unique(&dict, &spec, &Constraints::default());
+        let (first, second) = match &found {
+            Uniqueness::Ambiguous(first, second) => (first, second),
+            _ => panic!("Expected two answers: {:?}", found),
+        };
+        assert_eq!(
+            (first.word.as_str(), second.word.as_str()),
+            ("catsdogs", "catadogs")
+        );
+        // Starting with 'c' doesn't single out an answer, but each removed letter does.
+        let hints = suggest(&dict, &spec, &Constraints::default(), first, second);
+        assert_eq!(
+            hints,
+            vec![
+                Hint::Removed("s".to_string()),
+                Hint::Removed("a".to_string())
+            ]
+        );
+
+        let mut constraints = Constraints::default();
+        hints[0].apply(&mut constraints);
+        assert!(check_unique(&dict, &spec, &constraints).is_unique());
+        Hint::SuffixEnd('x').apply(&mut constraints);
+        assert_eq!(check_unique(&dict, &spec, &constraints), Uniqueness::None);
+    }
+
+    #[test]
+    fn test_generate() {
+        let dict = words(&[
+            "cat", "dogs", "catsdogs", "catadogs", "cow", "cowedogs", "batsdoge",
+        ]);
+        let found: Vec<(Hint, String, usize)> = generate(&dict, &SplitSpec::default())
+            .into_iter()
+            .map(|(hint, split, effort)| (hint, split.word, effort))
+            .collect();
+        // Every answer starts with 'c' and ends with 's', so only the removed letter tells them
+        // apart. "batsdoge" has to be checked for an 's' too, but doesn't split into words.
+        assert_eq!(
+            found,
+            vec![
+                (Hint::Removed("a".to_string()), "catadogs".to_string(), 1),
+                (Hint::Removed("e".to_string()), "cowedogs".to_string(), 1),
+                (Hint::Removed("s".to_string()), "catsdogs".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_removed_pairs() {
+        let dict = words(&[
+            "cat",
+            "cow",
+            "dogs",
+            "catabdogs",
+            "catbadogs",
+            "catcddogs",
+            "cowaadogs",
+        ]);
+        let spec = SplitSpec {
+            removed_len: 2,
+            ..SplitSpec::default()
+        };
+        let found: Vec<(Hint, String)> = generate(&dict, &spec)
+            .into_iter()
+            .map(|(hint, split, _)| (hint, split.word))
+            .collect();
+        // "ab" and "ba" are the same hint, which "aa" fits as well.
+        assert_eq!(
+            found,
+            vec![
+                (Hint::Removed("a".to_string()), "cowaadogs".to_string()),
+                (Hint::Removed("cd".to_string()), "catcddogs".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_near_splits() {
+        let details = |misses: Vec<NearMiss<Split>>| -> Vec<(String, Vec<String>)> {
+            misses
+                .into_iter()
+                .map(|miss| {
+                    let details = miss.violations.into_iter().map(|v| v.detail).collect();
+                    (miss.answer.word, details)
+                })
+                .collect()
+        };
+        // "fun" breaks two rules on its own. "sunfish" is too short to be cut at all.
+        let dict = words(&["sun", "fish", "sunkfish", "funkfish", "sunfish"]);
+        let constraints = Constraints {
+            prefix: PartConstraint {
+                pattern: Some(Regex::new("^s").unwrap()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let spec = SplitSpec::default();
+        assert_eq!(
+            details(near_splits(&dict, &spec, &constraints, 1)),
+            vec![("sunkfish".to_string(), vec![])]
+        );
+        assert_eq!(
+            details(near_splits(&dict, &spec, &constraints, 2)),
+            vec![
+                ("sunkfish".to_string(), vec![]),
+                (
+                    "funkfish".to_string(),
+                    vec![
+                        "fun isn't a word".to_string(),
+                        "fun doesn't match ^s".to_string()
+                    ]
+                ),
+            ]
+        );
+
+        // Only one of the two removed letters is allowed, which still breaks the rule once.
+        let dict = words(&["sun", "fish", "sunkkfish", "sunkyfish"]);
+        let spec = SplitSpec {
+            removed_len: 2,
+            ..spec
+        };
+        let constraints = Constraints {
+            removed_letters: Some(['k'].into_iter().collect()),
+            ..Default::default()
+        };
+        assert_eq!(
+            details(near_splits(&dict, &spec, &constraints, 1)),
+            vec![
+                ("sunkkfish".to_string(), vec![]),
+                (
+                    "sunkyfish".to_string(),
+                    vec!["ky can't be removed".to_string()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_spec_display() {
+        assert_eq!(SplitSpec::default().to_string(), "3|1|4");
+    }
+
+    #[test]
+    fn test_sweep_splits() {
+        let dict = words(&[
+            "a", "at", "ata", "abat", "cat", "dogs", "catsdogs", "catadogs", "cats", "hate",
+        ]);
+        let found = sweep_splits(&dict, &SweepLimits::default(), &Constraints::default());
+        let summary: Vec<(String, Vec<&str>)> = found
+            .iter()
+            .map(|(spec, splits)| {
+                (
+                    spec.to_string(),
+                    splits.iter().map(|s| s.word.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("1|1|1".to_string(), vec!["ata"]),
+                ("1|1|2".to_string(), vec!["abat"]),
+                ("3|1|4".to_string(), vec!["catsdogs", "catadogs"]),
+            ]
+        );
+
+        let limits = SweepLimits {
+            min_part_len: 2,
+            ..Default::default()
+        };
+        let found = sweep_splits(&dict, &limits, &Constraints::default());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[&SplitSpec::default()].len(), 2);
+
+        let limits = SweepLimits {
+            max_word_len: 4,
+            ..Default::default()
+        };
+        let found = sweep_splits(&dict, &limits, &Constraints::default());
+        assert!(!found.contains_key(&SplitSpec::default()));
+    }
+
+    #[test]
+    fn test_group_by_removed() {
+        let dict = words(&["cat", "dogs", "catsdogs", "catadogs", "cow", "cowadogs"]);
+        let groups = group_by_removed(find_splits(
+            &dict,
+            &SplitSpec::default(),
+            &Constraints::default(),
+        ));
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["a"].len(), 2);
+        assert_eq!(groups["s"].len(), 1);
+    }
+}
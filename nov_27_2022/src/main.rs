@@ -1,44 +1,287 @@
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::env;
+use std::io::Stdout;
 
-fn main() {
-    let f = match File::open("dictionary.txt") {
-        Ok(f) => f,
-        Err(why) => panic!("Failed to open dictionary.txt: {}", why),
+use nov_27_2022::{
+    check_unique, counted_splits, find_splits, generate, group_by_removed, near_splits, suggest,
+    sweep_splits, Constraints, Hint, Split, SplitSpec, SweepLimits,
+};
+use regex::Regex;
+use word_tools::dictionary::{load_words, read_words, WordFilter, FILTER_USAGE};
+use word_tools::generate::{self, article, GenerateOptions, Proposal, GENERATE_USAGE};
+use word_tools::near::{self, NearOptions, NEAR_USAGE};
+use word_tools::solution::{stdout_writer, OutputFormat, SolutionWriter, OUTPUT_USAGE};
+use word_tools::stats::{Stats, StatsFormat, STATS_USAGE};
+use word_tools::uniqueness::{self, Uniqueness, UNIQUE_USAGE};
+
+const USAGE: &str = "Usage: nov_27_2022 [options]
+  --prefix-len N      Length of the first part (default 3)
+  --removed-len N     Number of letters removed between the parts (default 1)
+  --suffix-len N      Length of the second part (default 4)
+  --prefix-list FILE  The first part must be in this word list
+  --suffix-list FILE  The second part must be in this word list
+  --prefix-regex RE   The first part must match this regex
+  --suffix-regex RE   The second part must match this regex
+  --removed LETTERS   The removed letter(s) must be among LETTERS
+  --group             Group the results by the removed letter(s)
+  --sweep             Try every word length and every cut position, grouped by pattern
+  --min-len N         With --sweep, skip words shorter than N
+  --max-len N         With --sweep, skip words longer than N
+  --min-part-len N    With --sweep, both parts must have at least N letters (default 1)";
+
+struct Config {
+    spec: SplitSpec,
+    constraints: Constraints,
+    group: bool,
+    sweep: bool,
+    check_unique: bool,
+    limits: SweepLimits,
+    filter: WordFilter,
+    output: OutputFormat,
+    stats: StatsFormat,
+    generate: GenerateOptions,
+    near: NearOptions,
+}
+
+fn parse_args() -> Result<Config, String> {
+    let mut config = Config {
+        spec: SplitSpec::default(),
+        constraints: Constraints::default(),
+        group: false,
+        sweep: false,
+        check_unique: false,
+        limits: SweepLimits::default(),
+        filter: WordFilter::default(),
+        output: OutputFormat::default(),
+        stats: StatsFormat::default(),
+        generate: GenerateOptions::default(),
+        near: NearOptions::default(),
     };
 
-    let mut fours = HashSet::new();
-    let mut threes = HashSet::new();
-    let mut eights = Vec::new();
-    for word in BufReader::new(f).lines().map(|s| s.unwrap()) {
-        // Use bytes so that we can use slice indexing.
-        let word = word.into_bytes();
-        match word.len() {
-            3 => {
-                threes.insert(word);
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if config.filter.parse_arg(&arg, &mut args)?
+            || config.output.parse_arg(&arg, &mut args)?
+            || config.stats.parse_arg(&arg, &mut args)?
+            || config.generate.parse_arg(&arg, &mut args)?
+            || config.near.parse_arg(&arg, &mut args)?
+        {
+            continue;
+        }
+        match arg.as_str() {
+            "--group" => {
+                config.group = true;
+                continue;
             }
-            4 => {
-                fours.insert(word);
+            "--sweep" => {
+                config.sweep = true;
+                continue;
+            }
+            "--check-unique" => {
+                config.check_unique = true;
+                continue;
             }
-            8 => eights.push(word),
             _ => (),
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", arg))?;
+        let parse_len = |value: &str| {
+            value
+                .parse::<usize>()
+                .map_err(|why| format!("Bad length for {}: {}", arg, why))
+        };
+        let parse_regex = |value: &str| {
+            Regex::new(value).map_err(|why| format!("Bad regex for {}: {}", arg, why))
         };
+        let word_list = |path: &str| read_words(path).into_iter().collect::<HashSet<_>>();
+        match arg.as_str() {
+            "--prefix-len" => config.spec.prefix_len = parse_len(&value)?,
+            "--removed-len" => config.spec.removed_len = parse_len(&value)?,
+            "--suffix-len" => config.spec.suffix_len = parse_len(&value)?,
+            "--prefix-list" => config.constraints.prefix.word_list = Some(word_list(&value)),
+            "--suffix-list" => config.constraints.suffix.word_list = Some(word_list(&value)),
+            "--prefix-regex" => config.constraints.prefix.pattern = Some(parse_regex(&value)?),
+            "--suffix-regex" => config.constraints.suffix.pattern = Some(parse_regex(&value)?),
+            "--min-len" => config.limits.min_word_len = parse_len(&value)?,
+            "--max-len" => config.limits.max_word_len = parse_len(&value)?,
+            "--min-part-len" => config.limits.min_part_len = parse_len(&value)?,
+            "--removed" => config.constraints.removed_letters = Some(value.chars().collect()),
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+    let single =
+        config.check_unique || config.generate.count.is_some() || config.near.allowed.is_some();
+    if config.sweep && single {
+        return Err(
+            "--check-unique, --generate and --near need a single pattern, not --sweep".to_string(),
+        );
     }
+    config.limits.removed_len = config.spec.removed_len;
+    Ok(config)
+}
 
-    for word in eights {
-        if !threes.contains(&word[..3]) {
-            continue;
+fn describe(split: &Split) -> String {
+    format!(
+        "{} {} {} {}",
+        split.word, split.prefix, split.removed, split.suffix
+    )
+}
+
+fn print_split(split: &Split) {
+    println!("{}", describe(split));
+}
+
+// The command line option that adds `hint`.
+fn hint_option(hint: &Hint) -> String {
+    match hint {
+        Hint::Removed(letters) => format!("--removed {}", letters),
+        Hint::PrefixStart(letter) => format!("--prefix-regex ^{}", letter),
+        Hint::SuffixEnd(letter) => format!("--suffix-regex {}$", letter),
+    }
+}
+
+// Prints `splits` as text, or writes them to `writer` if there is one. `pattern` is the cut they
+// were found with when sweeping.
+fn print_splits(
+    splits: Vec<Split>,
+    group: bool,
+    pattern: Option<&SplitSpec>,
+    writer: &mut Option<SolutionWriter<Stdout>>,
+) {
+    if let Some(writer) = writer {
+        for split in &splits {
+            let mut solution = split.to_solution();
+            if let Some(pattern) = pattern {
+                solution = solution.text("pattern", &pattern.to_string());
+            }
+            writer.write(&solution).unwrap();
         }
-        if !fours.contains(&word[4..]) {
-            continue;
+        return;
+    }
+    if group {
+        for (removed, group) in group_by_removed(splits) {
+            println!("Removed '{}': {}", removed, group.len());
+            for split in &group {
+                print_split(split);
+            }
+        }
+    } else {
+        for split in &splits {
+            print_split(split);
         }
+    }
+}
 
-        println!(
-            "{} {} {}",
-            std::str::from_utf8(&word).unwrap(),
-            std::str::from_utf8(&word[..3]).unwrap(),
-            std::str::from_utf8(&word[4..]).unwrap()
-        );
+// How a solver would be told about `hint`.
+fn hint_text(hint: &Hint) -> String {
+    match hint {
+        Hint::Removed(letters) if letters.len() == 1 => {
+            format!("the removed letter is {}", letters)
+        }
+        Hint::Removed(letters) => format!("the removed letters are {}", letters),
+        Hint::PrefixStart(letter) => format!("the first word starts with {}", letter),
+        Hint::SuffixEnd(letter) => format!("the second word ends with {}", letter),
+    }
+}
+
+// Proposes up to `count` puzzles with `spec`'s cut that have exactly one answer among the common
+// words.
+fn generate_puzzles(config: &Config, count: usize) -> Result<(), String> {
+    let common = config.generate.load()?;
+    let words = config.filter.apply(common.words().to_vec());
+    let spec = &config.spec;
+    let removed = match spec.removed_len {
+        1 => "a letter".to_string(),
+        n => format!("{} letters", n),
+    };
+    let proposals = generate(&words, spec)
+        .into_iter()
+        .map(|(hint, split, effort)| Proposal {
+            statement: format!(
+                "Find {} {}-letter word that is {} {}-letter word + {} + {} {}-letter word, where {}.",
+                article(spec.word_len()),
+                spec.word_len(),
+                article(spec.prefix_len),
+                spec.prefix_len,
+                removed,
+                article(spec.suffix_len),
+                spec.suffix_len,
+                hint_text(&hint)
+            ),
+            answer: describe(&split),
+            rarest: common.rarest([&split.word, &split.prefix, &split.suffix].map(|s| s.as_str())),
+            effort,
+        })
+        .collect();
+    generate::report(proposals, count, config.output).map_err(|why| why.to_string())
+}
+
+fn main() {
+    let config = match parse_args() {
+        Ok(config) => config,
+        Err(why) => {
+            eprintln!(
+                "{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}",
+                why,
+                USAGE,
+                UNIQUE_USAGE,
+                GENERATE_USAGE,
+                NEAR_USAGE,
+                FILTER_USAGE,
+                OUTPUT_USAGE,
+                STATS_USAGE
+            );
+            std::process::exit(1);
+        }
+    };
+
+    if let Some(count) = config.generate.count {
+        if let Err(why) = generate_puzzles(&config, count) {
+            eprintln!("{}", why);
+            std::process::exit(1);
+        }
+        return;
+    }
+    let mut stats = Stats::new();
+    let words = load_words("dictionary.txt", &config.filter);
+    stats.record("dictionary", words.len(), words.len());
+    if let Some(allowed) = config.near.allowed {
+        let misses = near_splits(&words, &config.spec, &config.constraints, allowed);
+        near::report(misses, describe, config.near.limit, config.output).unwrap();
+        return;
+    }
+    if config.check_unique {
+        let found = check_unique(&words, &config.spec, &config.constraints);
+        let suggestions: Vec<String> = match &found {
+            Uniqueness::Ambiguous(first, second) => {
+                suggest(&words, &config.spec, &config.constraints, first, second)
+                    .iter()
+                    .map(hint_option)
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        uniqueness::report(&found, describe, &suggestions, config.output).unwrap();
+        return;
+    }
+    let mut writer = stdout_writer(config.output);
+    if config.sweep {
+        let patterns = sweep_splits(&words, &config.limits, &config.constraints);
+        let found = patterns.values().map(|splits| splits.len()).sum();
+        stats.record("sweep", words.len(), found);
+        for (spec, splits) in patterns {
+            if writer.is_none() {
+                println!("Pattern {}: {}", spec, splits.len());
+            }
+            print_splits(splits, config.group, Some(&spec), &mut writer);
+        }
+    } else {
+        let splits = match config.stats {
+            StatsFormat::Off => find_splits(&words, &config.spec, &config.constraints),
+            _ => counted_splits(&words, &config.spec, &config.constraints, &mut stats),
+        };
+        print_splits(splits, config.group, None, &mut writer);
     }
+    stats.report(config.stats);
 }
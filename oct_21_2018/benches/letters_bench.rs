@@ -0,0 +1,148 @@
+#[macro_use]
+extern crate criterion;
+
+use criterion::Criterion;
+use std::collections::HashMap;
+
+extern crate oct_21_2018;
+extern crate word_tools;
+use oct_21_2018::beermouth::{four_word_candidates, shared_middle_letters};
+use word_tools::letters::LetterBag;
+
+// Three letter words that fit in "beermouth".
+const WORDS: &[&str] = &[
+    "bee", "bet", "bot", "bum", "but", "bye", "eme", "ere", "hem", "her", "hoe", "hot", "hub",
+    "hue", "hut", "met", "mob", "moe", "mot", "mud", "oat", "obe", "ohm", "ore", "our", "out",
+    "reb", "rem", "roe", "rot", "rub", "rue", "rum", "rut", "the", "toe", "tom", "tub", "tue",
+    "urb",
+];
+
+fn words() -> Vec<[char; 3]> {
+    WORDS
+        .iter()
+        .map(|word| {
+            let chars: Vec<char> = word.chars().collect();
+            [chars[0], chars[1], chars[2]]
+        })
+        .collect()
+}
+
+// The HashMap<char, u8> version of `four_word_candidates` that LetterBag replaced, kept here for
+// comparison.
+mod letter_map {
+    use std::collections::HashMap;
+
+    pub fn construct_letter_map(word: &str) -> HashMap<char, u8> {
+        let mut map = HashMap::new();
+        for letter in word.chars() {
+            *map.entry(letter).or_insert(0) += 1
+        }
+        map
+    }
+
+    pub fn letter_map_less_than(left: &HashMap<char, u8>, right: &HashMap<char, u8>) -> bool {
+        for (letter, l_count) in left {
+            match right.get(letter) {
+                Some(r_count) => {
+                    if l_count > r_count {
+                        return false;
+                    }
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    fn clear_two_letters(word: &[char; 3], letter_map: &mut HashMap<char, u8>) -> bool {
+        for letter in [word[0], word[2]].iter() {
+            match letter_map.get(letter) {
+                Some(count) if *count > 0 => {}
+                _ => return false,
+            }
+        }
+        *letter_map.get_mut(&word[0]).unwrap() -= 1;
+        *letter_map.get_mut(&word[2]).unwrap() -= 1;
+        true
+    }
+
+    fn four_word_product(words: &[[char; 3]], base_map: &HashMap<char, u8>) -> usize {
+        let mut out = 0;
+        let middle_letter = words[0][1];
+        for (ii, first) in words.iter().enumerate() {
+            let mut map = base_map.clone();
+            *map.get_mut(&middle_letter).unwrap() -= 1;
+            if !clear_two_letters(first, &mut map) {
+                continue;
+            }
+            for jj in ii + 1..words.len() {
+                let mut map = map.clone();
+                if !clear_two_letters(&words[jj], &mut map) {
+                    continue;
+                }
+                for kk in jj + 1..words.len() {
+                    let mut map = map.clone();
+                    if !clear_two_letters(&words[kk], &mut map) {
+                        continue;
+                    }
+                    for word in &words[kk + 1..] {
+                        let mut map = map.clone();
+                        if clear_two_letters(word, &mut map) {
+                            out += 1;
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    pub fn four_word_candidates(
+        shared_middle: &[Vec<[char; 3]>],
+        base_map: &HashMap<char, u8>,
+    ) -> usize {
+        shared_middle
+            .iter()
+            .map(|words| four_word_product(words, base_map))
+            .sum()
+    }
+}
+
+fn bench_subset(c: &mut Criterion) {
+    c.bench_function("subset_letter_bag", |b| {
+        let pool = LetterBag::from_word("beermouth").unwrap();
+        b.iter(|| {
+            WORDS
+                .iter()
+                .filter(|word| LetterBag::from_word(word).unwrap().is_subset(&pool))
+                .count()
+        });
+    });
+    c.bench_function("subset_letter_map", |b| {
+        let pool = letter_map::construct_letter_map("beermouth");
+        b.iter(|| {
+            WORDS
+                .iter()
+                .filter(|word| {
+                    letter_map::letter_map_less_than(&letter_map::construct_letter_map(word), &pool)
+                })
+                .count()
+        });
+    });
+}
+
+fn bench_four_words(c: &mut Criterion) {
+    c.bench_function("four_words_letter_bag", |b| {
+        let shared = shared_middle_letters(&words());
+        let pool = LetterBag::from_word("beermouth").unwrap();
+        b.iter(|| four_word_candidates(&shared, &pool).len());
+    });
+    c.bench_function("four_words_letter_map", |b| {
+        let shared = shared_middle_letters(&words());
+        let pool: HashMap<char, u8> = letter_map::construct_letter_map("beermouth");
+        b.iter(|| letter_map::four_word_candidates(&shared, &pool));
+    });
+}
+
+criterion_group!(benches, bench_subset, bench_four_words);
+criterion_main!(benches);
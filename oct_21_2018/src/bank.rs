@@ -0,0 +1,183 @@
+// Letter bank queries: which words can be spelled from a pool of letters, which sets of words use
+// up the whole pool, and which pairs of words rearrange it exactly ("make two words out of these
+// letters").
+
+use std::collections::{BTreeSet, HashMap};
+use word_tools::letters::LetterBag;
+
+pub struct Bank {
+    pool: LetterBag,
+    // Every distinct dictionary word that fits in the pool, sorted.
+    words: Vec<(String, LetterBag)>,
+}
+
+impl Bank {
+    pub fn new(dictionary: &[String], pool: LetterBag) -> Bank {
+        let mut words: Vec<(String, LetterBag)> = dictionary
+            .iter()
+            .filter_map(|word| LetterBag::from_word(word).map(|bag| (word.clone(), bag)))
+            .filter(|(word, bag)| !word.is_empty() && bag.is_subset(&pool))
+            .collect();
+        words.sort();
+        words.dedup();
+        Bank { pool, words }
+    }
+
+    // The words that can be spelled from the pool, longest first and then alphabetically.
+    // Optionally only words of length `len` or only words using `required`.
+    pub fn words(&self, len: Option<usize>, required: Option<char>) -> Vec<&str> {
+        let mut out: Vec<&str> = self
+            .words
+            .iter()
+            .filter(|(word, _)| len.is_none_or(|len| word.len() == len))
+            .filter(|(_, bag)| required.is_none_or(|letter| bag.contains(letter)))
+            .map(|(word, _)| word.as_str())
+            .collect();
+        out.sort_by_key(|word| std::cmp::Reverse(word.len()));
+        out
+    }
+
+    // The sets of words (at least `min_len` letters each) that use every letter of the pool
+    // exactly once and have as many words as possible. Each set is sorted.
+    pub fn partitions(&self, min_len: usize) -> Vec<Vec<&str>> {
+        let candidates: Vec<usize> = (0..self.words.len())
+            .filter(|&ii| self.words[ii].0.len() >= min_len.max(1))
+            .collect();
+        let mut search = PartitionSearch {
+            bank: self,
+            candidates,
+            min_len: min_len.max(1),
+            chosen: Vec::new(),
+            best: 0,
+            found: BTreeSet::new(),
+        };
+        search.cover(self.pool);
+        search
+            .found
+            .into_iter()
+            .map(|set| set.iter().map(|&ii| self.words[ii].0.as_str()).collect())
+            .collect()
+    }
+
+    // Pairs of words that together use every letter of the pool exactly once. Each pair is listed
+    // once, with the words in alphabetical order.
+    pub fn pairs(&self) -> Vec<(&str, &str)> {
+        let mut by_letters: HashMap<LetterBag, Vec<usize>> = HashMap::new();
+        for (ii, (_, bag)) in self.words.iter().enumerate() {
+            by_letters.entry(*bag).or_default().push(ii);
+        }
+        let mut out = Vec::new();
+        for (ii, (word, bag)) in self.words.iter().enumerate() {
+            let rest = self.pool.subtract(bag).unwrap();
+            if let Some(others) = by_letters.get(&rest) {
+                for &jj in others.iter().filter(|&&jj| jj >= ii) {
+                    out.push((word.as_str(), self.words[jj].0.as_str()));
+                }
+            }
+        }
+        out
+    }
+}
+
+struct PartitionSearch<'a> {
+    bank: &'a Bank,
+    // Indices of the words long enough to use.
+    candidates: Vec<usize>,
+    min_len: usize,
+    chosen: Vec<usize>,
+    // The most words in any partition found so far.
+    best: usize,
+    found: BTreeSet<Vec<usize>>,
+}
+
+impl PartitionSearch<'_> {
+    // Covers `left` with words. The first remaining letter has to be in one of the words, so only
+    // those words are tried at each step.
+    fn cover(&mut self, left: LetterBag) {
+        let first = match left.letters().next() {
+            Some(first) => first,
+            None => {
+                let mut set = self.chosen.clone();
+                set.sort_unstable();
+                if set.len() > self.best {
+                    self.best = set.len();
+                    self.found.clear();
+                }
+                if set.len() == self.best {
+                    self.found.insert(set);
+                }
+                return;
+            }
+        };
+        // Even if the rest were all the shortest allowed words, this can't catch up.
+        if self.chosen.len() + left.len() / self.min_len < self.best {
+            return;
+        }
+        for ii in 0..self.candidates.len() {
+            let word = self.candidates[ii];
+            let bag = &self.bank.words[word].1;
+            if !bag.contains(first) {
+                continue;
+            }
+            if let Some(rest) = left.subtract(bag) {
+                self.chosen.push(word);
+                self.cover(rest);
+                self.chosen.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bank(pool: &str, list: &[&str]) -> Bank {
+        let dictionary: Vec<String> = list.iter().map(|s| s.to_string()).collect();
+        Bank::new(&dictionary, LetterBag::from_word(pool).unwrap())
+    }
+
+    const WORDS: &[&str] = &[
+        "beer", "mouth", "bee", "hum", "rot", "our", "the", "tomb", "her", "me", "bum", "zoo",
+        "Bert", "hem", "tour", "mouths", "rebut", "home",
+    ];
+
+    #[test]
+    fn test_words() {
+        let bank = bank("beermouth", WORDS);
+        assert_eq!(
+            bank.words(None, None),
+            vec![
+                "mouth", "rebut", "beer", "home", "tomb", "tour", "bee", "bum", "hem", "her",
+                "hum", "our", "rot", "the", "me",
+            ]
+        );
+        assert_eq!(bank.words(Some(4), Some('o')), vec!["home", "tomb", "tour"]);
+        assert!(bank.words(Some(7), None).is_empty());
+    }
+
+    #[test]
+    fn test_pairs() {
+        let beermouth = bank("beermouth", WORDS);
+        assert_eq!(
+            beermouth.pairs(),
+            vec![("beer", "mouth"), ("home", "rebut")]
+        );
+        // A word can pair with itself.
+        assert_eq!(
+            bank("abab", &["ab", "ba"]).pairs(),
+            vec![("ab", "ab"), ("ab", "ba"), ("ba", "ba")]
+        );
+    }
+
+    #[test]
+    fn test_partitions() {
+        let bank = bank("beermouth", WORDS);
+        assert_eq!(bank.partitions(2), vec![vec!["bee", "hum", "rot"]]);
+        assert_eq!(
+            bank.partitions(4),
+            vec![vec!["beer", "mouth"], vec!["home", "rebut"]]
+        );
+        assert!(bank.partitions(6).is_empty());
+    }
+}
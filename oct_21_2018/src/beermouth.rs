@@ -0,0 +1,405 @@
+// The original solution to the Oct 21, 2018 puzzle. Every word through the center shares its
+// middle letter, so it picks four such words whose letters fit in "beermouth", tries each way of
+// placing them on the diagonals and middle lines, and then checks the words around the edge. The
+// four words are always different, so a grid that reads one word on two of those lines is left to
+// the general solver in `grid`. The functions are kept much as they were first written, so the
+// ones clippy objects to allow its style lints one by one.
+
+use grid::Grid;
+use std::collections::{HashMap, HashSet};
+use word_tools::letters::LetterBag;
+use word_tools::stats::Stats;
+
+// Gets the nth char from `word`.
+fn get_char(word: &str, index: usize) -> char {
+    word.chars().nth(index).unwrap()
+}
+
+// The middle letter has to be the middle letter of at least four words. Construct lists of
+// possible sets of words.
+pub fn shared_middle_letters(words: &[[char; 3]]) -> Vec<Vec<[char; 3]>> {
+    let mut map = HashMap::new();
+    for word in words {
+        map.entry(word[1]).or_insert(Vec::new()).push(*word)
+    }
+    let mut out = Vec::new();
+    for (_, val) in map.drain() {
+        if val.len() >= 4 {
+            out.push(val);
+        }
+    }
+    out
+}
+
+// Checks if the first and third letters of the word are still in `letters`, and if so removes
+// them. Ignores the middle letter since that is shared.
+// Returns whether or not the word was a subset.
+fn clear_two_letters(word: &[char; 3], letters: &mut LetterBag) -> bool {
+    let mut ends = LetterBag::new();
+    ends.insert(word[0]);
+    ends.insert(word[2]);
+    match letters.subtract(&ends) {
+        Some(rest) => {
+            *letters = rest;
+            true
+        }
+        None => false,
+    }
+}
+
+// If the word is a candidate, append it. Otherwise, do nothing. Returns whether or not the word
+// was appended.
+#[allow(clippy::extra_unused_lifetimes)]
+fn append_word_if<'a>(
+    word: &[char; 3],
+    letters: &mut LetterBag,
+    words: &mut Vec<[char; 3]>,
+) -> bool {
+    if !clear_two_letters(word, letters) {
+        return false;
+    }
+    words.push(*word);
+    true
+}
+
+#[allow(clippy::extra_unused_lifetimes, clippy::needless_range_loop)]
+fn four_word_product<'a>(words: &[[char; 3]], base: &LetterBag) -> Vec<Vec<[char; 3]>> {
+    let mut out = Vec::new();
+    let middle_letter = words[0][1];
+    for ii in 0..words.len() {
+        let mut letters = *base;
+        let mut four_words = Vec::new();
+        if !letters.remove(middle_letter) {
+            return out;
+        }
+        if !append_word_if(&words[ii], &mut letters, &mut four_words) {
+            continue;
+        }
+        assert_eq!(four_words.len(), 1);
+        for jj in ii + 1..words.len() {
+            // Truncate here so that we only get one word from this pass.
+            four_words.truncate(1);
+            // Copy the letters per iteration.
+            let mut letters = letters;
+            if !append_word_if(&words[jj], &mut letters, &mut four_words) {
+                continue;
+            }
+            assert_eq!(four_words.len(), 2);
+            for kk in jj + 1..words.len() {
+                four_words.truncate(2);
+                let mut letters = letters;
+                if !append_word_if(&words[kk], &mut letters, &mut four_words) {
+                    continue;
+                }
+                assert_eq!(four_words.len(), 3);
+                for ll in kk + 1..words.len() {
+                    four_words.truncate(3);
+                    let mut letters = letters;
+                    if append_word_if(&words[ll], &mut letters, &mut four_words) {
+                        assert_eq!(four_words.len(), 4);
+                        out.push(four_words.clone());
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+// Find all of the combinations of four words that share a middle letter and the combined letters
+// of all four words are still a subset of `base`.
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn four_word_candidates<'a>(
+    shared_middle: &[Vec<[char; 3]>],
+    base: &LetterBag,
+) -> Vec<Vec<[char; 3]>> {
+    let mut out = Vec::new();
+    for words in shared_middle {
+        out.append(&mut four_word_product(words, base));
+    }
+    out
+}
+
+// For four indices between zero and 4, return the missing index.
+fn missing_index(ii: usize, jj: usize, kk: usize) -> usize {
+    assert!(ii < 4);
+    assert!(jj < 4);
+    assert!(kk < 4);
+    for ll in 0..4 {
+        if ll != ii && ll != jj && ll != kk {
+            return ll;
+        }
+    }
+    unreachable!()
+}
+
+// Takes the candidates and finds words that make a proper word in the top row. The words are
+// returned such that the first word is the left to right diagonal (1, 5, 9 on a phone), the second
+// word is the middle vertical, the third is the right to left diagonal (3, 5, 7) and the remaining
+// word is last.
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn top_row_candidates<'a>(
+    four_cands: &Vec<Vec<[char; 3]>>,
+    possible_words: &HashSet<[char; 3]>,
+) -> Vec<Vec<[char; 3]>> {
+    let mut out = Vec::new();
+    for cand in four_cands {
+        assert!(cand.len() == 4);
+        for ii in 0..cand.len() {
+            let first = cand[ii];
+            for jj in 0..cand.len() {
+                if jj == ii {
+                    continue;
+                }
+                let second = cand[jj];
+                for kk in 0..cand.len() {
+                    if kk == jj || kk == ii {
+                        continue;
+                    }
+                    let third = cand[kk];
+                    let word = [first[0], second[0], third[0]];
+                    if possible_words.contains(&word) {
+                        let mut top_cand = vec![first, second, third];
+                        top_cand.push(cand[missing_index(ii, jj, kk)]);
+                        out.push(top_cand);
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+// Filter out sets of four where the bottom generated from the top three words isn't a word.
+#[allow(clippy::extra_unused_lifetimes, clippy::ptr_arg)]
+pub fn filter_by_bottom<'a>(cand: &Vec<[char; 3]>, possible_words: &HashSet<[char; 3]>) -> bool {
+    assert!(cand.len() == 4);
+    let first = cand[2][2];
+    let second = cand[1][2];
+    let third = cand[0][2];
+    let word = [first, second, third];
+    possible_words.contains(&word)
+}
+
+#[allow(clippy::extra_unused_lifetimes, clippy::ptr_arg)]
+pub fn filter_by_rest<'a>(cand: &Vec<[char; 3]>, possible_words: &HashSet<[char; 3]>) -> bool {
+    // The left vertical word is made up of the first letter of the first top word (left to
+    // right diagnol), the first letter of the remaining word, and the last letter of the
+    // third top word (right to left diagonal).
+    let left_v_word = [cand[0][0], cand[3][0], cand[2][2]];
+    if !possible_words.contains(&left_v_word) {
+        return false;
+    }
+    // The right vertical word is from the first letter of the third top word, the third letter
+    // of the remaining word and the third letter of the first top word.
+    let right_v_word = [cand[2][0], cand[3][2], cand[0][2]];
+    possible_words.contains(&right_v_word)
+}
+
+// Lays the four words out as a 3x3 grid.
+pub fn presentation_format(cand: &[[char; 3]]) -> Grid {
+    // Start from a blank grid so that anything not filled in shows up.
+    let mut out = Grid::blank(3).rows();
+    // The first word is the left to right diagonal.
+    out[0][0] = cand[0][0];
+    out[1][1] = cand[0][1];
+    out[2][2] = cand[0][2];
+    // The second word is the middle vertical.
+    out[0][1] = cand[1][0];
+    out[2][1] = cand[1][2];
+    // The third word is the left diagonal.
+    out[0][2] = cand[2][0];
+    out[2][0] = cand[2][2];
+    // The last word is the middle horizontal.
+    out[1][0] = cand[3][0];
+    out[1][2] = cand[3][2];
+    Grid::from_rows(&out)
+}
+
+// Returns the four words of each solution in the order `presentation_format` expects, once per
+// grid. `pool` is the letters to fill the grid with, "beermouth" in the original puzzle.
+pub fn candidates(dictionary: &[String], pool: &LetterBag) -> Vec<Vec<[char; 3]>> {
+    candidate_stages(dictionary, pool, None)
+}
+
+// `candidates`, recording how many candidates each stage keeps in `stats`. Recording reads the
+// clock and the peak memory, so only use this for --stats.
+pub fn counted_candidates(
+    dictionary: &[String],
+    pool: &LetterBag,
+    stats: &mut Stats,
+) -> Vec<Vec<[char; 3]>> {
+    candidate_stages(dictionary, pool, Some(stats))
+}
+
+fn candidate_stages(
+    dictionary: &[String],
+    pool: &LetterBag,
+    mut stats: Option<&mut Stats>,
+) -> Vec<Vec<[char; 3]>> {
+    let mut record = |name: &str, input: usize, output: usize| {
+        if let Some(stats) = stats.as_deref_mut() {
+            stats.record(name, input, output);
+        }
+    };
+    // Construct a vector of three letter words that are a subset of the pool.
+    let words: Vec<[char; 3]> = dictionary
+        .iter()
+        .filter(|s| s.len() == 3)
+        .filter(|s| LetterBag::from_word(s).is_some_and(|bag| bag.is_subset(pool)))
+        .map(|s| [get_char(s, 0), get_char(s, 1), get_char(s, 2)])
+        .collect();
+    record("three letter words", dictionary.len(), words.len());
+
+    // Find sets of words that share a middle letter.
+    let middle_candidates = shared_middle_letters(&words);
+    record("shared middle", words.len(), middle_candidates.len());
+    // Find sets of four words that share a middle letter and could make up the solution.
+    let four_cands = four_word_candidates(&middle_candidates, pool);
+    record("four words", middle_candidates.len(), four_cands.len());
+
+    // Find possible top rows.
+    let word_set: HashSet<[char; 3]> = words.iter().cloned().collect();
+    let top_cands = top_row_candidates(&four_cands, &word_set);
+    let top_len = top_cands.len();
+    record("top row", four_cands.len(), top_len);
+    let bottom_cands: Vec<Vec<[char; 3]>> = top_cands
+        .into_iter()
+        .filter(|cand| filter_by_bottom(cand, &word_set))
+        .collect();
+    record("bottom", top_len, bottom_cands.len());
+    let bottom_len = bottom_cands.len();
+    let rest_cands: Vec<Vec<[char; 3]>> = bottom_cands
+        .into_iter()
+        .filter(|cand| filter_by_rest(cand, &word_set))
+        .collect();
+    record("rest", bottom_len, rest_cands.len());
+    rest_cands
+}
+
+// Every grid `candidates` finds, as rows. Sorted so that it can be compared with the other
+// solvers.
+pub fn solve(dictionary: &[String], pool: &LetterBag) -> Vec<Vec<Vec<char>>> {
+    let mut out: Vec<Vec<Vec<char>>> = candidates(dictionary, pool)
+        .iter()
+        .map(|cand| presentation_format(cand).rows())
+        .collect();
+    out.sort();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::{self, GridSpec, Lexicon};
+
+    #[test]
+    fn test_general_solver_matches() {
+        // Made up words around the grid
+        //   h e m
+        //   o b u
+        //   t e r
+        // plus a few that only fit elsewhere or need letters beermouth doesn't have.
+        let dictionary: Vec<String> = [
+            "hem", "obu", "ter", "hot", "ebe", "mur", "hbr", "mbt", "tbm", "her", "bee", "zoo",
+            "them",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let expected = solve(&dictionary, &LetterBag::from_word("beermouth").unwrap());
+
+        let lexicon = Lexicon::new(dictionary.iter().map(|s| s.as_str()), 3);
+        let found: Vec<Vec<Vec<char>>> = grid::solve(&GridSpec::beermouth(), &lexicon)
+            .iter()
+            .map(|grid| grid.rows())
+            .collect();
+        assert_eq!(found, expected);
+        // The grid above and its transpose.
+        assert_eq!(found.len(), 2);
+    }
+
+    #[test]
+    fn test_counted_candidates() {
+        // The grid from `test_general_solver_matches` goes through the center with obu, ebe, hbr
+        // and mbt. Only three words have e in the middle, so the shared middle stage drops them.
+        let dictionary: Vec<String> = [
+            "hem", "obu", "ter", "hot", "ebe", "mur", "hbr", "mbt", "her", "zoo", "them",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let mut stats = Stats::new();
+        let found = counted_candidates(
+            &dictionary,
+            &LetterBag::from_word("beermouth").unwrap(),
+            &mut stats,
+        );
+        let names: Vec<&str> = stats.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "three letter words",
+                "shared middle",
+                "four words",
+                "top row",
+                "bottom",
+                "rest"
+            ]
+        );
+        assert_eq!((stats.stages[0].input, stats.stages[0].output), (11, 9));
+        assert_eq!((stats.stages[1].input, stats.stages[1].output), (9, 1));
+        // Each stage starts from what the one before it kept.
+        for pair in stats.stages[2..].windows(2) {
+            assert_eq!(pair[0].output, pair[1].input);
+        }
+        assert_eq!(stats.stages[5].output, found.len());
+        // Its transpose would need tbm.
+        assert_eq!(found.len(), 1);
+    }
+
+    #[test]
+    #[allow(
+        clippy::needless_range_loop,
+        clippy::unnecessary_sort_by,
+        clippy::vec_init_then_push
+    )]
+    fn test_shared_middle() {
+        let mut words = Vec::new();
+        words.push(['b', 'e', 'e']);
+        words.push(['b', 'e', 't']);
+        words.push(['m', 'e', 't']);
+        words.push(['b', 'u', 'm']);
+        words.push(['b', 'u', 't']);
+        words.push(['o', 'u', 't']);
+        words.push(['o', 'u', 'f']);
+        words.push(['b', 'o', 't']);
+        words.push(['h', 'o', 't']);
+        words.push(['m', 'o', 't']);
+        words.push(['t', 'o', 'm']);
+        words.push(['r', 'o', 't']);
+        let mut expected = Vec::new();
+        {
+            let mut group = Vec::new();
+            for ii in 3..7 {
+                group.push(words[ii]);
+            }
+            group.sort();
+            expected.push(group);
+        }
+        {
+            let mut group = Vec::new();
+            for ii in 7..12 {
+                group.push(words[ii]);
+            }
+            group.sort();
+            expected.push(group);
+        }
+        let mut input_words = words.clone();
+        input_words.sort();
+        let mut shared = shared_middle_letters(&input_words);
+        // Sort by the lengths of the sub-Vecs to prevent test flakes.
+        shared.sort_by(|l, r| l.len().cmp(&r.len()));
+        assert_eq!(expected, shared);
+    }
+}
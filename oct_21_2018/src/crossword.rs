@@ -0,0 +1,294 @@
+// An American style crossword: every run of two or more white squares across or down is a slot
+// that has to be filled with a word. Slots are filled one at a time with backtracking, always
+// taking the slot with the fewest words that still fit next, and no word is used twice.
+
+use grid::BLANK;
+use std::collections::{HashMap, HashSet};
+
+pub const BLACK: char = '#';
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Across,
+    Down,
+}
+
+impl Direction {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Direction::Across => "across",
+            Direction::Down => "down",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Slot {
+    // The clue number. Squares that start a slot are numbered left to right, top to bottom.
+    pub number: usize,
+    pub direction: Direction,
+    // Row major indices in reading order.
+    pub cells: Vec<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Crossword {
+    pub width: usize,
+    pub height: usize,
+    // Row major: `BLACK`, `BLANK` or a letter.
+    pub cells: Vec<char>,
+}
+
+impl Crossword {
+    // Parses rows separated by slashes, with `BLACK` for black squares, `BLANK` for the squares
+    // to fill and letters for squares that are given, e.g. "..#/.../#..".
+    pub fn parse(pattern: &str) -> Option<Crossword> {
+        let rows: Vec<Vec<char>> = pattern
+            .split('/')
+            .map(|row| row.chars().collect())
+            .collect();
+        let width = rows[0].len();
+        let valid = |ch: &char| ch.is_ascii_lowercase() || *ch == BLANK || *ch == BLACK;
+        if width == 0
+            || rows
+                .iter()
+                .any(|row| row.len() != width || !row.iter().all(valid))
+        {
+            return None;
+        }
+        Some(Crossword {
+            width,
+            height: rows.len(),
+            cells: rows.concat(),
+        })
+    }
+
+    fn is_white(&self, row: usize, col: usize) -> bool {
+        row < self.height && col < self.width && self.cells[row * self.width + col] != BLACK
+    }
+
+    // Every slot, in clue number order with across before down.
+    pub fn slots(&self) -> Vec<Slot> {
+        let mut out = Vec::new();
+        let mut number = 0;
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if !self.is_white(row, col) {
+                    continue;
+                }
+                let across =
+                    (col == 0 || !self.is_white(row, col - 1)) && self.is_white(row, col + 1);
+                let down =
+                    (row == 0 || !self.is_white(row - 1, col)) && self.is_white(row + 1, col);
+                if !across && !down {
+                    continue;
+                }
+                number += 1;
+                if across {
+                    let cells = (col..)
+                        .take_while(|&c| self.is_white(row, c))
+                        .map(|c| row * self.width + c)
+                        .collect();
+                    out.push(Slot {
+                        number,
+                        direction: Direction::Across,
+                        cells,
+                    });
+                }
+                if down {
+                    let cells = (row..)
+                        .take_while(|&r| self.is_white(r, col))
+                        .map(|r| r * self.width + col)
+                        .collect();
+                    out.push(Slot {
+                        number,
+                        direction: Direction::Down,
+                        cells,
+                    });
+                }
+            }
+        }
+        out
+    }
+
+    pub fn word(&self, slot: &Slot) -> String {
+        slot.cells.iter().map(|&cell| self.cells[cell]).collect()
+    }
+}
+
+struct Filler<'a> {
+    slots: Vec<Slot>,
+    // The dictionary words by length.
+    words: &'a HashMap<usize, Vec<Vec<char>>>,
+    puzzle: Crossword,
+    filled: Vec<bool>,
+    used: HashSet<Vec<char>>,
+    limit: usize,
+    out: Vec<Crossword>,
+}
+
+impl<'a> Filler<'a> {
+    // The unused words that agree with the letters already in `slot`.
+    fn candidates(&self, slot: &Slot) -> Vec<&'a Vec<char>> {
+        let words = match self.words.get(&slot.cells.len()) {
+            Some(words) => words,
+            None => return Vec::new(),
+        };
+        words
+            .iter()
+            .filter(|word| {
+                slot.cells.iter().zip(word.iter()).all(|(&cell, &letter)| {
+                    let current = self.puzzle.cells[cell];
+                    current == BLANK || current == letter
+                })
+            })
+            .filter(|word| !self.used.contains(*word))
+            .collect()
+    }
+
+    fn fill(&mut self) {
+        if self.out.len() >= self.limit {
+            return;
+        }
+        // Pick the open slot with the fewest candidates. A slot with none means a dead end.
+        let mut best: Option<(usize, Vec<&'a Vec<char>>)> = None;
+        for (ii, slot) in self.slots.iter().enumerate() {
+            if self.filled[ii] {
+                continue;
+            }
+            let candidates = self.candidates(slot);
+            let empty = candidates.is_empty();
+            if best
+                .as_ref()
+                .is_none_or(|(_, best)| candidates.len() < best.len())
+            {
+                best = Some((ii, candidates));
+            }
+            if empty {
+                break;
+            }
+        }
+        let (slot, candidates) = match best {
+            Some(best) => best,
+            None => {
+                self.out.push(self.puzzle.clone());
+                return;
+            }
+        };
+        let cells = self.slots[slot].cells.clone();
+        let before: Vec<char> = cells.iter().map(|&cell| self.puzzle.cells[cell]).collect();
+        self.filled[slot] = true;
+        for word in candidates {
+            for (&cell, &letter) in cells.iter().zip(word.iter()) {
+                self.puzzle.cells[cell] = letter;
+            }
+            self.used.insert(word.clone());
+            self.fill();
+            self.used.remove(word);
+        }
+        for (&cell, &letter) in cells.iter().zip(before.iter()) {
+            self.puzzle.cells[cell] = letter;
+        }
+        self.filled[slot] = false;
+    }
+}
+
+// Finds up to `limit` ways to fill `puzzle` with words from `dictionary`. Words with anything
+// other than a-z in them are ignored.
+pub fn fill(puzzle: &Crossword, dictionary: &[String], limit: usize) -> Vec<Crossword> {
+    let mut words: HashMap<usize, Vec<Vec<char>>> = HashMap::new();
+    for word in dictionary {
+        if word.chars().all(|ch| ch.is_ascii_lowercase()) {
+            words
+                .entry(word.len())
+                .or_default()
+                .push(word.chars().collect());
+        }
+    }
+    for list in words.values_mut() {
+        list.sort();
+        list.dedup();
+    }
+    let slots = puzzle.slots();
+    let mut filler = Filler {
+        filled: vec![false; slots.len()],
+        slots,
+        words: &words,
+        puzzle: puzzle.clone(),
+        used: HashSet::new(),
+        limit,
+        out: Vec::new(),
+    };
+    filler.fill();
+    filler.out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary(words: &[&str]) -> Vec<String> {
+        words.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_slots() {
+        let puzzle = Crossword::parse("..#/.../#..").unwrap();
+        let slots: Vec<(usize, Direction, Vec<usize>)> = puzzle
+            .slots()
+            .into_iter()
+            .map(|slot| (slot.number, slot.direction, slot.cells))
+            .collect();
+        assert_eq!(
+            slots,
+            vec![
+                (1, Direction::Across, vec![0, 1]),
+                (1, Direction::Down, vec![0, 3]),
+                (2, Direction::Down, vec![1, 4, 7]),
+                (3, Direction::Across, vec![3, 4, 5]),
+                (4, Direction::Down, vec![5, 8]),
+                (5, Direction::Across, vec![7, 8]),
+            ]
+        );
+        assert_eq!(Crossword::parse("..#/.."), None);
+        assert_eq!(Crossword::parse("a?/.."), None);
+    }
+
+    #[test]
+    fn test_fill() {
+        let puzzle = Crossword::parse("..#/.../#..").unwrap();
+        let words = dictionary(&["ab", "ac", "bdf", "cde", "eg", "fg", "zz", "bdz", "Bd"]);
+        let found = fill(&puzzle, &words, 10);
+        // The grid is its own transpose, so the fill can be flipped.
+        assert_eq!(
+            found,
+            vec![
+                Crossword::parse("ab#/cde/#fg").unwrap(),
+                Crossword::parse("ac#/bdf/#eg").unwrap(),
+            ]
+        );
+        let slots = puzzle.slots();
+        assert_eq!(found[0].word(&slots[2]), "bdf");
+
+        // A given letter that nothing fits.
+        let puzzle = Crossword::parse("..#/.../#.z").unwrap();
+        assert!(fill(&puzzle, &words, 10).is_empty());
+    }
+
+    #[test]
+    fn test_no_repeats() {
+        // Every fill of a 2x2 block would use a word both across and down.
+        let puzzle = Crossword::parse("../..").unwrap();
+        assert!(fill(&puzzle, &dictionary(&["ab", "ba"]), 10).is_empty());
+        assert!(fill(&puzzle, &dictionary(&["ab", "ba", "aa", "bb"]), 10).is_empty());
+        let words = dictionary(&["ab", "cd", "ac", "bd"]);
+        assert_eq!(
+            fill(&puzzle, &words, 10),
+            vec![
+                Crossword::parse("ab/cd").unwrap(),
+                Crossword::parse("ac/bd").unwrap(),
+            ]
+        );
+        assert_eq!(fill(&puzzle, &words, 1).len(), 1);
+    }
+}
@@ -0,0 +1,990 @@
+// A general N by N version of the puzzle: fill a grid from a pool of letters (or from any letters)
+// so that a chosen set of lines all read as words. The cells are filled in row major order with
+// backtracking, and every line is checked against the prefixes of the dictionary as soon as one
+// of its letters is placed. Some cells can be given up front and some words can be required to
+// appear on one of the lines. The search can also be allowed to break a few of those rules, to
+// find near misses when a dictionary gap leaves no exact answer.
+
+use render::Drawing;
+use std::collections::HashSet;
+use std::fmt;
+use word_tools::letters::LetterBag;
+use word_tools::near::{NearMiss, Rule, Violation};
+use word_tools::symmetry::Symmetry;
+use word_tools::uniqueness::{unique_hints, Uniqueness};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Line {
+    Row(usize),
+    Column(usize),
+    // Top left to bottom right.
+    Diagonal,
+    // Top right to bottom left.
+    AntiDiagonal,
+}
+
+impl Line {
+    // The cells of the line as row major indices, in reading order.
+    pub fn cells(&self, size: usize) -> Vec<usize> {
+        match *self {
+            Line::Row(row) => (0..size).map(|col| row * size + col).collect(),
+            Line::Column(col) => (0..size).map(|row| row * size + col).collect(),
+            Line::Diagonal => (0..size).map(|ii| ii * size + ii).collect(),
+            Line::AntiDiagonal => (0..size).map(|ii| ii * size + size - 1 - ii).collect(),
+        }
+    }
+}
+
+// A line that has to form a word, either read normally or backwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Reading {
+    pub line: Line,
+    pub reversed: bool,
+}
+
+impl Reading {
+    // The cells of the reading in the order its word is read.
+    pub fn cells(&self, size: usize) -> Vec<usize> {
+        let mut cells = self.line.cells(size);
+        if self.reversed {
+            cells.reverse();
+        }
+        cells
+    }
+
+    // The name `parse_readings` knows it by, e.g. "row-0" or "reversed-diagonal".
+    pub fn name(&self) -> String {
+        let line = match self.line {
+            Line::Row(row) => format!("row-{}", row),
+            Line::Column(col) => format!("column-{}", col),
+            Line::Diagonal => "diagonal".to_string(),
+            Line::AntiDiagonal => "anti-diagonal".to_string(),
+        };
+        if self.reversed {
+            format!("reversed-{}", line)
+        } else {
+            line
+        }
+    }
+}
+
+// Parses a comma separated list of lines: rows, columns, diagonals (both), diagonal,
+// anti-diagonal, row-N or column-N (zero based). Prefixing any of them with "reversed-" requires
+// the backwards reading to be a word instead.
+pub fn parse_readings(size: usize, names: &str) -> Option<Vec<Reading>> {
+    let mut out = Vec::new();
+    for name in names.split(',') {
+        let (reversed, name) = match name.strip_prefix("reversed-") {
+            Some(name) => (true, name),
+            None => (false, name),
+        };
+        let lines = match name {
+            "rows" => (0..size).map(Line::Row).collect(),
+            "columns" => (0..size).map(Line::Column).collect(),
+            "diagonals" => vec![Line::Diagonal, Line::AntiDiagonal],
+            "diagonal" => vec![Line::Diagonal],
+            "anti-diagonal" => vec![Line::AntiDiagonal],
+            _ => {
+                let (kind, index) = name.split_once('-')?;
+                let index: usize = index.parse().ok()?;
+                if index >= size {
+                    return None;
+                }
+                match kind {
+                    "row" => vec![Line::Row(index)],
+                    "column" => vec![Line::Column(index)],
+                    _ => return None,
+                }
+            }
+        };
+        out.extend(lines.into_iter().map(|line| Reading { line, reversed }));
+    }
+    Some(out)
+}
+
+#[derive(Clone, Debug)]
+pub struct GridSpec {
+    pub size: usize,
+    pub readings: Vec<Reading>,
+    // The letters that may be used, with how many times each may appear, including the letters
+    // given in `pattern`. None allows any letter any number of times.
+    pub letters: Option<LetterBag>,
+    // The letters already filled in. The rest of the cells are `BLANK`.
+    pub pattern: Grid,
+    // Words that have to be one of the readings.
+    pub required: Vec<String>,
+}
+
+impl GridSpec {
+    // An empty grid with no required words.
+    pub fn new(size: usize, readings: Vec<Reading>, letters: Option<LetterBag>) -> GridSpec {
+        GridSpec {
+            size,
+            readings,
+            letters,
+            pattern: Grid::blank(size),
+            required: Vec::new(),
+        }
+    }
+
+    // The Oct 21, 2018 puzzle: a 3x3 grid from the letters of "beermouth" where every row, column
+    // and diagonal is a word.
+    pub fn beermouth() -> GridSpec {
+        GridSpec::new(
+            3,
+            parse_readings(3, "rows,columns,diagonals").unwrap(),
+            LetterBag::from_word("beermouth"),
+        )
+    }
+
+    // The rotations and reflections that turn the required readings into each other, so that
+    // applying one to a solution always gives another solution. Reversed readings matter: the
+    // transpose of the anti-diagonal reads bottom to top, so rows, columns and diagonals read
+    // forwards are only symmetric under the identity. The given letters have to stay put too.
+    pub fn symmetries(&self) -> Vec<Symmetry> {
+        let cells: HashSet<Vec<usize>> = self
+            .readings
+            .iter()
+            .map(|reading| reading.cells(self.size))
+            .collect();
+        Symmetry::all()
+            .iter()
+            .cloned()
+            .filter(|&symmetry| self.pattern.transform(symmetry) == self.pattern)
+            .filter(|symmetry| {
+                let destinations = symmetry.destinations(self.size);
+                cells.iter().all(|reading| {
+                    let moved: Vec<usize> =
+                        reading.iter().map(|&cell| destinations[cell]).collect();
+                    cells.contains(&moved)
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Grid {
+    pub size: usize,
+    // Row major.
+    pub cells: Vec<char>,
+}
+
+// The cell of a pattern that hasn't been filled in.
+pub const BLANK: char = '.';
+
+impl Grid {
+    pub fn blank(size: usize) -> Grid {
+        Grid {
+            size,
+            cells: vec![BLANK; size * size],
+        }
+    }
+
+    // Parses rows separated by slashes, with `BLANK` for the cells to fill, e.g. "h.m/.b./...".
+    pub fn parse(pattern: &str) -> Option<Grid> {
+        let rows: Vec<Vec<char>> = pattern
+            .split('/')
+            .map(|row| row.chars().collect())
+            .collect();
+        let valid = |ch: &char| ch.is_ascii_lowercase() || *ch == BLANK;
+        if rows
+            .iter()
+            .any(|row| row.len() != rows.len() || !row.iter().all(valid))
+        {
+            return None;
+        }
+        Some(Grid::from_rows(&rows))
+    }
+
+    pub fn from_rows(rows: &[Vec<char>]) -> Grid {
+        Grid {
+            size: rows.len(),
+            cells: rows.iter().flat_map(|row| row.iter().cloned()).collect(),
+        }
+    }
+
+    pub fn rows(&self) -> Vec<Vec<char>> {
+        self.cells
+            .chunks(self.size)
+            .map(|row| row.to_vec())
+            .collect()
+    }
+
+    pub fn transform(&self, symmetry: Symmetry) -> Grid {
+        Grid {
+            size: self.size,
+            cells: symmetry.apply(self.size, &self.cells),
+        }
+    }
+
+    // The words along `readings`.
+    pub fn words(&self, readings: &[Reading]) -> Vec<String> {
+        readings
+            .iter()
+            .map(|reading| self.reading(reading))
+            .collect()
+    }
+
+    // One row per line with the letters spaced out, so that a pattern and its solutions line up.
+    pub fn render(&self) -> String {
+        Drawing::grid(self).text()
+    }
+
+    pub fn reading(&self, reading: &Reading) -> String {
+        let mut letters: Vec<char> = reading
+            .line
+            .cells(self.size)
+            .iter()
+            .map(|&cell| self.cells[cell])
+            .collect();
+        if reading.reversed {
+            letters.reverse();
+        }
+        letters.into_iter().collect()
+    }
+}
+
+// Rows separated by slashes, as `Grid::parse` takes them.
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<String> = self
+            .cells
+            .chunks(self.size)
+            .map(|row| row.iter().collect())
+            .collect();
+        write!(f, "{}", rows.join("/"))
+    }
+}
+
+// The words of one length along with every prefix of them, read forwards and backwards.
+pub struct Lexicon {
+    len: usize,
+    words: HashSet<Vec<char>>,
+    prefixes: HashSet<Vec<char>>,
+    reversed_words: HashSet<Vec<char>>,
+    reversed_prefixes: HashSet<Vec<char>>,
+    letters: Vec<char>,
+}
+
+impl Lexicon {
+    pub fn new<'a, I>(words: I, len: usize) -> Lexicon
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut lexicon = Lexicon {
+            len,
+            words: HashSet::new(),
+            prefixes: HashSet::new(),
+            reversed_words: HashSet::new(),
+            reversed_prefixes: HashSet::new(),
+            letters: Vec::new(),
+        };
+        let mut letters = HashSet::new();
+        for word in words {
+            let word: Vec<char> = word.chars().collect();
+            if word.len() != len {
+                continue;
+            }
+            letters.extend(word.iter().cloned());
+            // Cells are filled in reading order for normal readings, so a reversed reading is
+            // filled from its last letter and has to be checked against the reversed words.
+            let reversed: Vec<char> = word.iter().rev().cloned().collect();
+            for end in 1..=len {
+                lexicon.prefixes.insert(word[..end].to_vec());
+                lexicon.reversed_prefixes.insert(reversed[..end].to_vec());
+            }
+            lexicon.words.insert(word);
+            lexicon.reversed_words.insert(reversed);
+        }
+        lexicon.letters = letters.into_iter().collect();
+        lexicon.letters.sort_unstable();
+        lexicon
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.chars().collect::<Vec<char>>())
+    }
+}
+
+struct Search<'a> {
+    spec: &'a GridSpec,
+    lexicon: &'a Lexicon,
+    // The cells of each reading in forward order.
+    reading_cells: Vec<Vec<usize>>,
+    // For each cell, the readings that pass through it along with the cell's position in each.
+    readings_by_cell: Vec<Vec<(usize, usize)>>,
+    cells: Vec<char>,
+    pool: Option<LetterBag>,
+    required: Vec<Vec<char>>,
+    // For each symmetry being skipped, the cell of the grid that lands on each cell.
+    symmetry_sources: Vec<Vec<usize>>,
+    // Stop once this many grids have been found.
+    limit: usize,
+    out: Vec<Grid>,
+    // How many partial grids were tried, as a measure of how hard the puzzle is.
+    nodes: usize,
+    // How many rules a grid may break: readings that aren't words and letters beyond the pool.
+    allowed: usize,
+    // How many are broken so far.
+    used: usize,
+    // The readings that have stopped being words.
+    broken: Vec<bool>,
+}
+
+impl<'a> Search<'a> {
+    // Whether the letters placed so far along every reading through `cell` can still be a word.
+    // Readings that can't are marked broken instead while fewer than `allowed` rules are broken,
+    // and returned so that they can be mended when backtracking. None if that's too many.
+    fn check_readings(&mut self, cell: usize) -> Option<Vec<usize>> {
+        let mut broken = Vec::new();
+        for ii in 0..self.readings_by_cell[cell].len() {
+            let (reading, position) = self.readings_by_cell[cell][ii];
+            if self.broken[reading] {
+                continue;
+            }
+            let letters: Vec<char> = self.reading_cells[reading][..=position]
+                .iter()
+                .map(|&c| self.cells[c])
+                .collect();
+            let complete = position + 1 == self.spec.size;
+            let ok = match (self.spec.readings[reading].reversed, complete) {
+                (false, false) => self.lexicon.prefixes.contains(&letters),
+                (false, true) => self.lexicon.words.contains(&letters),
+                (true, false) => self.lexicon.reversed_prefixes.contains(&letters),
+                (true, true) => self.lexicon.reversed_words.contains(&letters),
+            };
+            if ok {
+                continue;
+            }
+            if self.used == self.allowed {
+                self.mend(&broken);
+                return None;
+            }
+            self.broken[reading] = true;
+            self.used += 1;
+            broken.push(reading);
+        }
+        Some(broken)
+    }
+
+    // Undoes `check_readings`.
+    fn mend(&mut self, broken: &[usize]) {
+        for &reading in broken {
+            self.broken[reading] = false;
+        }
+        self.used -= broken.len();
+    }
+
+    // Whether the grid can still be the smallest (in row major order) of its images under the
+    // skipped symmetries. Only the cells up to `cell` have been filled.
+    fn smallest_image(&self, cell: usize) -> bool {
+        for sources in &self.symmetry_sources {
+            for (position, &source) in sources.iter().enumerate().take(cell + 1) {
+                if source > cell {
+                    break;
+                }
+                if self.cells[position] != self.cells[source] {
+                    if self.cells[position] > self.cells[source] {
+                        return false;
+                    }
+                    break;
+                }
+            }
+        }
+        true
+    }
+
+    // Whether every required word is on one of the readings of the finished grid.
+    fn has_required(&self) -> bool {
+        let words: HashSet<Vec<char>> = self
+            .spec
+            .readings
+            .iter()
+            .map(|reading| {
+                reading
+                    .cells(self.spec.size)
+                    .iter()
+                    .map(|&cell| self.cells[cell])
+                    .collect()
+            })
+            .collect();
+        self.required.iter().all(|word| words.contains(word))
+    }
+
+    fn fill(&mut self, cell: usize) {
+        if self.out.len() >= self.limit {
+            return;
+        }
+        self.nodes += 1;
+        if cell == self.cells.len() {
+            // Near misses are checked against every rule by the caller.
+            if self.allowed > 0 || self.has_required() {
+                self.out.push(Grid {
+                    size: self.spec.size,
+                    cells: self.cells.clone(),
+                });
+            }
+            return;
+        }
+        let given = self.spec.pattern.cells[cell];
+        if given != BLANK {
+            // Its letter was taken out of the pool up front.
+            self.cells[cell] = given;
+            if !self.smallest_image(cell) {
+                return;
+            }
+            if let Some(broken) = self.check_readings(cell) {
+                self.fill(cell + 1);
+                self.mend(&broken);
+            }
+            return;
+        }
+        // Letters the pool has run out of break a rule each.
+        let (candidates, extras): (Vec<char>, Vec<char>) = match &self.pool {
+            Some(pool) if self.used < self.allowed => (
+                pool.letters().collect(),
+                self.lexicon
+                    .letters
+                    .iter()
+                    .cloned()
+                    .filter(|&letter| !pool.contains(letter))
+                    .collect(),
+            ),
+            Some(pool) => (pool.letters().collect(), Vec::new()),
+            None => (self.lexicon.letters.clone(), Vec::new()),
+        };
+        let candidates = candidates.into_iter().map(|letter| (letter, false));
+        for (letter, extra) in candidates.chain(extras.into_iter().map(|letter| (letter, true))) {
+            self.cells[cell] = letter;
+            if !self.smallest_image(cell) {
+                continue;
+            }
+            self.used += extra as usize;
+            if let Some(broken) = self.check_readings(cell) {
+                if let Some(pool) = &mut self.pool {
+                    pool.remove(letter);
+                }
+                self.fill(cell + 1);
+                if let Some(pool) = &mut self.pool {
+                    if !extra {
+                        pool.insert(letter);
+                    }
+                }
+                self.mend(&broken);
+            }
+            self.used -= extra as usize;
+        }
+    }
+}
+
+// Finds every grid satisfying `spec` using the words in `lexicon`, which must be `spec.size`
+// letters long.
+pub fn solve(spec: &GridSpec, lexicon: &Lexicon) -> Vec<Grid> {
+    search(spec, lexicon, &[], usize::MAX, 0).0
+}
+
+// Like `solve`, but only finds the smallest grid of each set of solutions that are rotations or
+// reflections of each other under `spec.symmetries()`. The rest are never searched.
+pub fn solve_distinct(spec: &GridSpec, lexicon: &Lexicon) -> Vec<Grid> {
+    search(spec, lexicon, &spec.symmetries(), usize::MAX, 0).0
+}
+
+// Whether `spec` has exactly one solution, counting the rotations and reflections of a grid
+// under `spec.symmetries()` as the same solution.
+pub fn check_unique(spec: &GridSpec, lexicon: &Lexicon) -> Uniqueness<Grid> {
+    Uniqueness::check(search(spec, lexicon, &spec.symmetries(), 2, 0).0)
+}
+
+// New puzzles like `spec` but with each of `pools` as the letters: the pools that fill the grid
+// in exactly one way (up to symmetry), each with its grid and the number of partial grids the
+// search tried. Pools that aren't `spec.size` squared letters from a-z are skipped.
+pub fn generate<'a>(
+    spec: &GridSpec,
+    lexicon: &Lexicon,
+    pools: &[&'a str],
+) -> Vec<(&'a str, Grid, usize)> {
+    let mut out = Vec::new();
+    for &pool in pools {
+        if pool.chars().count() != spec.size * spec.size {
+            continue;
+        }
+        let letters = match LetterBag::from_word(pool) {
+            Some(letters) => letters,
+            None => continue,
+        };
+        let spec = GridSpec {
+            letters: Some(letters),
+            ..spec.clone()
+        };
+        let (mut grids, nodes) = search(&spec, lexicon, &spec.symmetries(), 2, 0);
+        if grids.len() == 1 {
+            out.push((pool, grids.remove(0), nodes));
+        }
+    }
+    out
+}
+
+// The rules `grid` breaks: readings that aren't words, letters beyond what `spec.letters` gives
+// and required words that aren't on any reading.
+pub fn violations(spec: &GridSpec, lexicon: &Lexicon, grid: &Grid) -> Vec<Violation> {
+    let mut out = Vec::new();
+    for reading in &spec.readings {
+        let word = grid.reading(reading);
+        if !lexicon.contains(&word) {
+            let detail = format!("{} reads {}", reading.name(), word);
+            out.push(Violation::new(Rule::Word, detail));
+        }
+    }
+    if let Some(letters) = &spec.letters {
+        let mut pool = *letters;
+        for &letter in &grid.cells {
+            if pool.remove(letter) {
+                continue;
+            }
+            let detail = if letters.contains(letter) {
+                format!("one {} too many", letter)
+            } else {
+                format!("{} isn't one of the letters", letter)
+            };
+            out.push(Violation::new(Rule::Letters, detail));
+        }
+    }
+    let words = grid.words(&spec.readings);
+    for word in spec.required.iter().filter(|word| !words.contains(word)) {
+        out.push(Violation::new(
+            Rule::Other,
+            format!("{} isn't on any line", word),
+        ));
+    }
+    out
+}
+
+// The grids that break at most `allowed` of `spec`'s rules, as `violations` counts them, one of
+// each set of rotations and reflections under `spec.symmetries()`. Given letters in the pattern
+// are always kept.
+pub fn near(spec: &GridSpec, lexicon: &Lexicon, allowed: usize) -> Vec<NearMiss<Grid>> {
+    search(spec, lexicon, &spec.symmetries(), usize::MAX, allowed)
+        .0
+        .into_iter()
+        .map(|grid| NearMiss {
+            violations: violations(spec, lexicon, &grid),
+            answer: grid,
+        })
+        .filter(|miss| miss.violations.len() <= allowed)
+        .collect()
+}
+
+// An extra constraint a puzzle setter could add to rule out other grids.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Hint {
+    // A word that has to be one of the readings.
+    Require(String),
+    // A letter given in one of the cells.
+    Letter { cell: usize, letter: char },
+}
+
+impl Hint {
+    pub fn apply(&self, spec: &mut GridSpec) {
+        match self {
+            Hint::Require(word) => spec.required.push(word.clone()),
+            Hint::Letter { cell, letter } => spec.pattern.cells[*cell] = *letter,
+        }
+    }
+}
+
+// The hints that each leave exactly one solution, when `first` and `second` both solve `spec`.
+// Candidates are the words on the readings of one grid but not the other and the letters where
+// the grids differ.
+pub fn suggest(spec: &GridSpec, lexicon: &Lexicon, first: &Grid, second: &Grid) -> Vec<Hint> {
+    let mut hints = Vec::new();
+    for (grid, other) in [(first, second), (second, first)] {
+        let others = other.words(&spec.readings);
+        for word in grid.words(&spec.readings) {
+            let hint = Hint::Require(word.clone());
+            if !others.contains(&word) && !spec.required.contains(&word) && !hints.contains(&hint) {
+                hints.push(hint);
+            }
+        }
+    }
+    for (grid, other) in [(first, second), (second, first)] {
+        for (cell, (&letter, &theirs)) in grid.cells.iter().zip(other.cells.iter()).enumerate() {
+            if letter != theirs {
+                hints.push(Hint::Letter { cell, letter });
+            }
+        }
+    }
+    unique_hints(hints, |hint| {
+        let mut spec = spec.clone();
+        hint.apply(&mut spec);
+        check_unique(&spec, lexicon)
+    })
+}
+
+// Up to `limit` grids that break no more than `allowed` rules, skipping images under the `skip`
+// symmetries, along with the number of partial grids tried.
+fn search(
+    spec: &GridSpec,
+    lexicon: &Lexicon,
+    skip: &[Symmetry],
+    limit: usize,
+    allowed: usize,
+) -> (Vec<Grid>, usize) {
+    assert_eq!(spec.size, lexicon.len);
+    assert_eq!(spec.size, spec.pattern.size);
+    let size = spec.size;
+    let mut pool = spec.letters;
+    let mut used = 0;
+    if let Some(pool) = &mut pool {
+        for &given in spec.pattern.cells.iter().filter(|&&ch| ch != BLANK) {
+            if !pool.remove(given) {
+                used += 1;
+            }
+        }
+    }
+    if used > allowed {
+        return (Vec::new(), 0);
+    }
+    let reading_cells: Vec<Vec<usize>> = spec
+        .readings
+        .iter()
+        .map(|reading| reading.line.cells(size))
+        .collect();
+    let mut readings_by_cell = vec![Vec::new(); size * size];
+    for (reading, cells) in reading_cells.iter().enumerate() {
+        for (position, &cell) in cells.iter().enumerate() {
+            readings_by_cell[cell].push((reading, position));
+        }
+    }
+    let symmetry_sources = skip
+        .iter()
+        .filter(|&&symmetry| symmetry != Symmetry::Identity)
+        .map(|symmetry| {
+            let mut sources = vec![0; size * size];
+            for (cell, dest) in symmetry.destinations(size).into_iter().enumerate() {
+                sources[dest] = cell;
+            }
+            sources
+        })
+        .collect();
+    let mut search = Search {
+        spec,
+        lexicon,
+        reading_cells,
+        readings_by_cell,
+        cells: vec![' '; size * size],
+        pool,
+        required: spec
+            .required
+            .iter()
+            .map(|word| word.chars().collect())
+            .collect(),
+        symmetry_sources,
+        limit,
+        out: Vec::new(),
+        nodes: 0,
+        allowed,
+        used,
+        broken: vec![false; spec.readings.len()],
+    };
+    search.fill(0);
+    (search.out, search.nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Grid {
+        let rows: Vec<Vec<char>> = rows.iter().map(|row| row.chars().collect()).collect();
+        Grid::from_rows(&rows)
+    }
+
+    #[test]
+    fn test_line_cells() {
+        assert_eq!(Line::Row(1).cells(3), vec![3, 4, 5]);
+        assert_eq!(Line::Column(2).cells(3), vec![2, 5, 8]);
+        assert_eq!(Line::Diagonal.cells(3), vec![0, 4, 8]);
+        assert_eq!(Line::AntiDiagonal.cells(3), vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn test_parse_readings() {
+        assert_eq!(
+            parse_readings(3, "rows,columns,diagonals").unwrap().len(),
+            8
+        );
+        let readings = parse_readings(4, "reversed-row-3,anti-diagonal").unwrap();
+        assert_eq!(
+            readings,
+            vec![
+                Reading {
+                    line: Line::Row(3),
+                    reversed: true,
+                },
+                Reading {
+                    line: Line::AntiDiagonal,
+                    reversed: false,
+                },
+            ]
+        );
+        let names: Vec<String> = readings.iter().map(|reading| reading.name()).collect();
+        assert_eq!(names, vec!["reversed-row-3", "anti-diagonal"]);
+        assert_eq!(parse_readings(3, "row-3"), None);
+        assert_eq!(parse_readings(3, "spiral"), None);
+    }
+
+    #[test]
+    fn test_reading() {
+        let g = grid(&["hem", "obu", "ter"]);
+        let read = |line, reversed| g.reading(&Reading { line, reversed });
+        assert_eq!(read(Line::Row(0), false), "hem");
+        assert_eq!(read(Line::Column(0), false), "hot");
+        assert_eq!(read(Line::AntiDiagonal, false), "mbt");
+        assert_eq!(read(Line::Row(2), true), "ret");
+    }
+
+    #[test]
+    fn test_solve_word_square() {
+        let words = ["bit", "ice", "ten", "bat", "tan"];
+        let lexicon = Lexicon::new(words.iter().cloned(), 3);
+        let spec = GridSpec::new(3, parse_readings(3, "rows,columns").unwrap(), None);
+        assert_eq!(solve(&spec, &lexicon), vec![grid(&["bit", "ice", "ten"])]);
+    }
+
+    #[test]
+    fn test_symmetries() {
+        let spec = |lines| GridSpec::new(3, parse_readings(3, lines).unwrap(), None);
+        assert_eq!(GridSpec::beermouth().symmetries(), vec![Symmetry::Identity]);
+        assert_eq!(
+            spec("rows,columns").symmetries(),
+            vec![Symmetry::Identity, Symmetry::Transpose]
+        );
+        assert_eq!(
+            spec("rows,columns,reversed-rows,reversed-columns").symmetries(),
+            Symmetry::all().to_vec()
+        );
+        assert_eq!(spec("row-1,column-1").symmetries().len(), 2);
+    }
+
+    #[test]
+    fn test_solve_distinct() {
+        let words = ["bit", "ice", "ten", "ace", "rot", "bar", "ico", "tet"];
+        let lexicon = Lexicon::new(words.iter().cloned(), 3);
+        let spec = GridSpec::new(3, parse_readings(3, "rows,columns").unwrap(), None);
+        let all = solve(&spec, &lexicon);
+        let distinct = solve_distinct(&spec, &lexicon);
+        // "bar/ice/tet" and its transpose "bit/ace/ret" are the same solution.
+        // "bar/ico/tet" and its transpose "bit/ace/rot" are the same solution.
+        assert_eq!(all.len(), 4);
+        assert_eq!(
+            distinct,
+            vec![
+                grid(&["bar", "ico", "tet"]),
+                grid(&["bit", "ice", "ten"]),
+                grid(&["bit", "ice", "tet"]),
+            ]
+        );
+        for g in &distinct {
+            assert!(all.contains(&g.transform(Symmetry::Transpose)));
+        }
+    }
+
+    #[test]
+    fn test_solve_beermouth() {
+        // Made up words so that exactly one arrangement of "beermouth" works.
+        let words = [
+            "hem", "obu", "ter", "hot", "ebe", "mur", "hbr", "mbt", "her",
+        ];
+        let lexicon = Lexicon::new(words.iter().cloned(), 3);
+        assert_eq!(
+            solve(&GridSpec::beermouth(), &lexicon),
+            vec![grid(&["hem", "obu", "ter"])]
+        );
+    }
+
+    #[test]
+    fn test_solve_reversed() {
+        let words = ["ab", "ba", "bb"];
+        let lexicon = Lexicon::new(words.iter().cloned(), 2);
+        let spec = GridSpec::new(
+            2,
+            parse_readings(2, "row-0,reversed-row-1,reversed-columns").unwrap(),
+            LetterBag::from_word("abbb"),
+        );
+        // Row 1 read backwards and both columns read backwards must be words.
+        let found = solve(&spec, &lexicon);
+        for g in &found {
+            assert!(lexicon.contains(&g.reading(&spec.readings[0])));
+            assert!(lexicon.contains(&g.reading(&spec.readings[1])));
+        }
+        assert_eq!(
+            found,
+            vec![
+                grid(&["ab", "bb"]),
+                grid(&["ba", "bb"]),
+                grid(&["bb", "ab"]),
+                grid(&["bb", "ba"]),
+            ]
+        );
+        // Without "ba" the 'a' has to be read first in both directions.
+        let lexicon = Lexicon::new(["ab", "bb"].iter().cloned(), 2);
+        assert_eq!(solve(&spec, &lexicon), vec![grid(&["bb", "ba"])]);
+    }
+
+    #[test]
+    fn test_parse_pattern() {
+        let pattern = Grid::parse("h.m/.b./...").unwrap();
+        assert_eq!(pattern.cells, "h.m.b....".chars().collect::<Vec<char>>());
+        assert_eq!(pattern.render(), " h  .  m \n .  b  . \n .  .  . \n");
+        assert_eq!(Grid::parse("ab/c"), None);
+        assert_eq!(Grid::parse("aB/cd"), None);
+    }
+
+    #[test]
+    fn test_solve_pattern() {
+        let words = ["bit", "ice", "ten", "ace", "rot", "bar", "ico", "tet"];
+        let lexicon = Lexicon::new(words.iter().cloned(), 3);
+        let mut spec = GridSpec::new(3, parse_readings(3, "rows,columns").unwrap(), None);
+        spec.pattern = Grid::parse("b../.c./..n").unwrap();
+        assert_eq!(solve(&spec, &lexicon), vec![grid(&["bit", "ice", "ten"])]);
+        // The given letters come out of the pool.
+        spec.letters = LetterBag::from_word("bitcetn");
+        assert!(solve(&spec, &lexicon).is_empty());
+        spec.letters = LetterBag::from_word("bitcetnie");
+        assert_eq!(solve(&spec, &lexicon).len(), 1);
+
+        let mut spec = GridSpec::new(3, parse_readings(3, "rows,columns").unwrap(), None);
+        spec.required = vec!["rot".to_string()];
+        // It's the last row of one grid and the last column of its transpose.
+        assert_eq!(
+            solve(&spec, &lexicon),
+            vec![grid(&["bar", "ico", "tet"]), grid(&["bit", "ace", "rot"])]
+        );
+        assert_eq!(
+            solve_distinct(&spec, &lexicon),
+            vec![grid(&["bar", "ico", "tet"])]
+        );
+    }
+
+    #[test]
+    fn test_uniqueness() {
+        let words = ["bit", "ice", "ten", "ace", "rot", "bar", "ico", "tet"];
+        let lexicon = Lexicon::new(words.iter().cloned(), 3);
+        let spec = GridSpec::new(3, parse_readings(3, "rows,columns").unwrap(), None);
+        let (first, second) = match check_unique(&spec, &lexicon) {
+            Uniqueness::Ambiguous(first, second) => (first, second),
+            other => panic!("Expected two grids: {:?}", other),
+        };
+        assert_eq!(first.to_string(), "bar/ico/tet");
+        assert_eq!(second.to_string(), "bit/ice/ten");
+
+        // "tet" and "ice" are also in "bit/ice/tet", and so is a 't' in the corner.
+        let require = |word: &str| Hint::Require(word.to_string());
+        let letter = |cell, letter| Hint::Letter { cell, letter };
+        assert_eq!(
+            suggest(&spec, &lexicon, &first, &second),
+            vec![
+                require("bar"),
+                require("ico"),
+                require("ace"),
+                require("rot"),
+                require("ten"),
+                letter(1, 'a'),
+                letter(2, 'r'),
+                letter(5, 'o'),
+                letter(8, 'n'),
+            ]
+        );
+
+        let mut unique = spec.clone();
+        letter(8, 'n').apply(&mut unique);
+        assert_eq!(unique.pattern.to_string(), ".../.../..n");
+        assert_eq!(
+            check_unique(&unique, &lexicon),
+            Uniqueness::Unique(grid(&["bit", "ice", "ten"]))
+        );
+        require("rot").apply(&mut unique);
+        assert_eq!(check_unique(&unique, &lexicon), Uniqueness::None);
+    }
+
+    #[test]
+    fn test_generate() {
+        let words = ["bit", "ice", "ten", "ace", "rot", "bar", "ico", "tet"];
+        let lexicon = Lexicon::new(words.iter().cloned(), 3);
+        let spec = GridSpec::new(3, parse_readings(3, "rows,columns").unwrap(), None);
+        // The second pool is too short and the third can't be filled.
+        let found = generate(&spec, &lexicon, &["biticeten", "bariotet", "abcdefghi"]);
+        assert_eq!(found.len(), 1);
+        let (pool, grid, nodes) = &found[0];
+        assert_eq!(*pool, "biticeten");
+        assert_eq!(grid.to_string(), "bit/ice/ten");
+        // At least one partial grid per cell on the way to the answer.
+        assert!(*nodes > 9);
+    }
+
+    // Each near miss as the grid and the details of the rules it breaks.
+    fn near_details(
+        spec: &GridSpec,
+        lexicon: &Lexicon,
+        allowed: usize,
+    ) -> Vec<(String, Vec<String>)> {
+        near(spec, lexicon, allowed)
+            .into_iter()
+            .map(|miss| {
+                let details = miss.violations.into_iter().map(|v| v.detail).collect();
+                (miss.answer.to_string(), details)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_near_last_cell() {
+        // Row 1 of no/ox is only finished, and broken, by the very last letter.
+        let lexicon = Lexicon::new(["no", "on"].iter().cloned(), 2);
+        let readings = parse_readings(2, "rows,column-0").unwrap();
+        let spec = GridSpec::new(2, readings, LetterBag::from_word("noox"));
+        assert!(solve(&spec, &lexicon).is_empty());
+        assert_eq!(
+            near_details(&spec, &lexicon, 1),
+            vec![
+                ("no/ox".to_string(), vec!["row-1 reads ox".to_string()]),
+                ("no/on".to_string(), vec!["one n too many".to_string()]),
+                ("on/no".to_string(), vec!["one n too many".to_string()]),
+                ("ox/no".to_string(), vec!["row-0 reads ox".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_near_given_letter() {
+        // The given x isn't one of the letters, which uses up the only rule that may be broken.
+        let lexicon = Lexicon::new(["xa", "ab", "ba"].iter().cloned(), 2);
+        let readings = parse_readings(2, "rows,columns").unwrap();
+        let mut spec = GridSpec::new(2, readings, LetterBag::from_word("aabb"));
+        spec.pattern = Grid::parse("x./..").unwrap();
+        assert!(near(&spec, &lexicon, 0).is_empty());
+        assert_eq!(
+            near_details(&spec, &lexicon, 1),
+            vec![(
+                "xa/ab".to_string(),
+                vec!["x isn't one of the letters".to_string()]
+            )]
+        );
+        // Giving an x takes it out of the pool like any other letter.
+        spec.letters = LetterBag::from_word("xaab");
+        assert_eq!(solve(&spec, &lexicon), vec![grid(&["xa", "ab"])]);
+    }
+
+    #[test]
+    fn test_pattern_symmetries() {
+        let mut spec = GridSpec::new(3, parse_readings(3, "rows,columns").unwrap(), None);
+        spec.pattern = Grid::parse("b../.../...").unwrap();
+        assert_eq!(
+            spec.symmetries(),
+            vec![Symmetry::Identity, Symmetry::Transpose]
+        );
+        spec.pattern = Grid::parse(".b./.../...").unwrap();
+        assert_eq!(spec.symmetries(), vec![Symmetry::Identity]);
+    }
+}
@@ -0,0 +1,8 @@
+extern crate word_tools;
+
+pub mod bank;
+pub mod beermouth;
+pub mod crossword;
+pub mod grid;
+pub mod render;
+pub mod squares;
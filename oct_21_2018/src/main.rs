@@ -4,389 +4,709 @@
   A (paraphrased) statement of the problem:
     Given the letters in the word 'beermouth', contruct a three by three matrix of words where all
     the verticals, horizontals, and diagonals form three letter words.
+
+  With no arguments it solves that puzzle. It can also solve general word grids, fill crosswords,
+  find word squares and answer letter bank questions, with the options in USAGE below. Either way,
+  solutions that are rotations or reflections of each other are printed once, along with how they
+  are related. --stats covers every mode but --check-unique and --near.
 */
 
-use std::collections::{HashMap, HashSet};
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::vec::Vec;
+extern crate oct_21_2018;
+extern crate word_tools;
+
+use oct_21_2018::bank::Bank;
+use oct_21_2018::beermouth::{candidates, counted_candidates, presentation_format};
+use oct_21_2018::crossword::{self, Crossword};
+use oct_21_2018::grid::{
+    self, check_unique, parse_readings, solve, solve_distinct, suggest, Grid, GridSpec, Hint,
+    Lexicon, BLANK,
+};
+use oct_21_2018::render::{html, Drawing, Format};
+use oct_21_2018::squares::{self, SquareKind, SquareSpec};
+use std::env;
+use std::io::Stdout;
+use word_tools::dictionary::{load_words, WordFilter, FILTER_USAGE};
+use word_tools::flags;
+use word_tools::generate::{self, GenerateOptions, Proposal, GENERATE_USAGE};
+use word_tools::letters::LetterBag;
+use word_tools::near::{self, NearOptions, NEAR_USAGE};
+use word_tools::solution::{stdout_writer, OutputFormat, Solution, SolutionWriter, OUTPUT_USAGE};
+use word_tools::stats::{Stats, StatsFormat, STATS_USAGE};
+use word_tools::symmetry::{classify, Symmetry, SymmetryClass};
+use word_tools::uniqueness::{self, Uniqueness, UNIQUE_USAGE};
+
+const USAGE: &str = "Usage: oct_21_2018 [options]
+General grids (see src/grid.rs), which default to the same puzzle:
+  --grid                Use the general backtracking solver
+  --size N              The width and height of the grid (default 3)
+  --letters LETTERS     The letters to fill the grid with, or any for no restriction
+  --lines LIST          The lines that must be words, e.g. rows,columns,diagonals,reversed-row-0
+  --skip-symmetric      Don't search grids that are rotations or reflections of one already
+                        searched, using only the symmetries that keep the lines and --pattern
+  --pattern ROWS        Letters already filled in, e.g. h.m/.b./... with '.' for the cells to fill;
+                        the size comes from the pattern and --letters include the given ones
+  --require WORD        A word that has to be one of the lines (can be given more than once)
+--pattern, --require, --check-unique, --generate and --near imply --grid. --generate tries each
+common word with as many letters as the grid has cells as the letters, and --near counts lines
+that aren't words, letters beyond --letters and missing --require words as broken rules.
+
+Crosswords (see src/crossword.rs):
+  --crossword ROWS      Fill a crossword such as ..#../...../#...# with '#' for black squares and
+                        '.' for squares to fill; every run of two or more squares gets a new word
+  --count N             How many fills to print (default 1)
+
+Word squares (see src/squares.rs):
+  --square KIND         classic (the columns are the rows) or double (every word differs), with
+                        --size 3 to 7, --letters and --count
+  --diagonal            The top left to bottom right diagonal has to be a word too
+  --anti-diagonal       So does the top right to bottom left one
+
+Letter banks (see src/bank.rs):
+  --bank QUERY          About --letters: words (narrowed down with --length N and --must-use C),
+                        partitions (the most words that use up every letter, each at least
+                        --min-len N long, default 2) or pairs (two words using every letter once)
+
+Drawing grids, crosswords and squares (see src/render.rs):
+  --format FORMAT       text (default), boxes, svg for one image per grid or html for one page
+  --highlight           Mark the lines that had to be words and list them";
 
-// Gets the nth char from `word`.
-fn get_char(word: &str, index: usize) -> char {
-    word.chars().nth(index).unwrap()
+// Reads the dictionary with `filter` applied, keeping words that are `len` letters long.
+fn read_words(filter: &WordFilter, len: Option<usize>) -> Vec<String> {
+    load_words("dictionary.txt", filter)
+        .into_iter()
+        .filter(|s| len.is_none_or(|len| s.chars().count() == len))
+        .collect()
 }
 
-// Convert the word to a map from charaters to the number of times that character appears.
-fn construct_letter_map(word: &str) -> HashMap<char, u8> {
-    let mut map = HashMap::new();
-    for letter in word.chars() {
-        *map.entry(letter).or_insert(0) += 1
-    }
-    map
+// Where results go: printed as they're found, collected into one page for HTML, or written as
+// machine readable solutions. The search stages are counted in `stats` along the way.
+struct Report {
+    format: Format,
+    highlight: bool,
+    title: &'static str,
+    notes: Vec<String>,
+    page: Vec<(String, Drawing)>,
+    writer: Option<SolutionWriter<Stdout>>,
+    stats: Stats,
+    stats_format: StatsFormat,
+    filter: WordFilter,
 }
 
-// Compare if `left` is a subset of `right`.
-fn letter_map_less_than(left: &HashMap<char, u8>, right: &HashMap<char, u8>) -> bool {
-    for (letter, l_count) in left {
-        match right.get(letter) {
-            Some(r_count) => if l_count > r_count {
-                return false;
-            },
-            _ => return false,
+impl Report {
+    fn new(
+        format: Format,
+        output: OutputFormat,
+        stats_format: StatsFormat,
+        filter: &WordFilter,
+        highlight: bool,
+        title: &'static str,
+    ) -> Report {
+        Report {
+            format,
+            highlight,
+            title,
+            notes: Vec::new(),
+            page: Vec::new(),
+            writer: stdout_writer(output),
+            stats: Stats::new(),
+            stats_format,
+            filter: filter.clone(),
         }
     }
-    true
-}
 
-// The middle letter has to be the middle letter of at least four words. Construct lists of
-// possible sets of words.
-fn shared_middle_letters(words: &[[char; 3]]) -> Vec<Vec<[char; 3]>> {
-    let mut map = HashMap::new();
-    for word in words {
-        map.entry(word[1]).or_insert(Vec::new()).push(*word)
-    }
-    let mut out = Vec::new();
-    for (_, val) in map.drain() {
-        if val.len() >= 4 {
-            out.push(val);
+    // Reports one result, drawn or as `solution`.
+    fn result(&mut self, caption: &str, drawing: Drawing, solution: Solution) {
+        match &mut self.writer {
+            Some(writer) => writer.write(&solution).unwrap(),
+            None => self.picture(caption, drawing),
         }
     }
-    out
-}
 
-// Checks that the first and last letters of `word` are still in `right`.
-fn two_letter_less_than(word: &[char; 3], right: &HashMap<char, u8>) -> bool {
-    for letter in [word[0], word[2]].iter() {
-        match right.get(letter) {
-            Some(count) => if *count <= 0 {
-                return false;
-            },
-            _ => return false,
+    // Commentary and pictures other than results are left out of machine readable output.
+    fn note(&mut self, text: &str) {
+        if self.writer.is_some() {
+            return;
+        }
+        match self.format {
+            Format::Text | Format::Boxes => println!("{}", text),
+            Format::Svg => println!("<!-- {} -->", text.replace("--", "- -")),
+            Format::Html => self.notes.push(text.to_string()),
         }
     }
-    true
-}
-
-// Checks if the word is a subset of the letters in `letter_map`. If so, it decrements the counts
-// in `letter_map` by the first and third letters of the word. Ignores the middle letter since that
-// is shared.
-// Returns whether or not the word was a subset.
-fn clear_two_letters(word: &[char; 3], letter_map: &mut HashMap<char, u8>) -> bool {
-    if !two_letter_less_than(word, letter_map) {
-        return false;
-    }
-    *letter_map.get_mut(&word[0]).unwrap() -= 1;
-    *letter_map.get_mut(&word[2]).unwrap() -= 1;
-    true
-}
-
-// If the word is a candidate, append it. Otherwise, do nothing. Returns whether or not the word
-// was appended.
-fn append_word_if<'a>(
-    word: &[char; 3],
-    letter_map: &mut HashMap<char, u8>,
-    words: &mut Vec<[char; 3]>,
-) -> bool {
-    if !clear_two_letters(word, letter_map) {
-        return false;
-    }
-    words.push(*word);
-    true
-}
 
-fn four_word_product<'a>(words: &[[char; 3]], base_map: &HashMap<char, u8>) -> Vec<Vec<[char; 3]>> {
-    let mut out = Vec::new();
-    let middle_letter = words[0][1];
-    for ii in 0..words.len() {
-        let mut map = base_map.clone();
-        let mut four_words = Vec::new();
-        *map.get_mut(&middle_letter).unwrap() -= 1;
-        if !append_word_if(&words[ii], &mut map, &mut four_words) {
-            continue;
+    // Shows `drawing` under `caption`. The text formats list any highlighted lines; the images
+    // only color them with --highlight.
+    fn picture(&mut self, caption: &str, mut drawing: Drawing) {
+        if self.writer.is_some() {
+            return;
         }
-        assert_eq!(four_words.len(), 1);
-        for jj in ii + 1..words.len() {
-            // Truncate here so that we only get one word from this pass.
-            four_words.truncate(1);
-            // Create a new map per iteration.
-            let mut map = map.clone();
-            if !append_word_if(&words[jj], &mut map, &mut four_words) {
-                continue;
-            }
-            assert_eq!(four_words.len(), 2);
-            for kk in jj + 1..words.len() {
-                four_words.truncate(2);
-                let mut map = map.clone();
-                if !append_word_if(&words[kk], &mut map, &mut four_words) {
-                    continue;
+        if !self.highlight && (self.format == Format::Svg || self.format == Format::Html) {
+            drawing.highlights.clear();
+        }
+        match self.format {
+            Format::Text | Format::Boxes => {
+                if !caption.is_empty() {
+                    println!("{}", caption);
                 }
-                assert_eq!(four_words.len(), 3);
-                for ll in kk + 1..words.len() {
-                    four_words.truncate(3);
-                    let mut map = map.clone();
-                    if append_word_if(&words[ll], &mut map, &mut four_words) {
-                        assert_eq!(four_words.len(), 4);
-                        out.push(four_words.clone());
-                    }
+                if self.format == Format::Text {
+                    print!("{}", drawing.text());
+                } else {
+                    print!("{}", drawing.boxes());
                 }
+                print!("{}", drawing.legend());
+                println!();
             }
+            Format::Svg => {
+                if !caption.is_empty() {
+                    self.note(caption);
+                }
+                println!("{}", drawing.svg());
+            }
+            Format::Html => self.page.push((caption.to_string(), drawing)),
+        }
+    }
+
+    fn finish(self) {
+        if self.format == Format::Html && self.writer.is_none() {
+            print!("{}", html(self.title, &self.notes, &self.page));
         }
+        self.stats.report(self.stats_format);
+    }
+
+    // Reads the words of the dictionary that are `len` letters long, counting it as a stage.
+    fn read_words(&mut self, len: Option<usize>) -> Vec<String> {
+        let words = read_words(&self.filter, len);
+        self.stats.record("dictionary", words.len(), words.len());
+        words
     }
-    out
 }
 
-// Find all of the combinations of four words that share a middle letter and the combined letters
-// of all four words are still a subset of 'base_map'.
-fn four_word_candidates<'a>(
-    shared_middle: &[Vec<[char; 3]>],
-    base_map: &HashMap<char, u8>,
-) -> Vec<Vec<[char; 3]>> {
-    let mut out = Vec::new();
-    for words in shared_middle {
-        out.append(&mut four_word_product(words, base_map));
-    }
-    out
+// A grid standing for the solutions in `class`.
+fn class_solution(drawing: &Drawing, class: &SymmetryClass<char>) -> Solution {
+    Solution::new()
+        .list("rows", drawing.rows())
+        .number("solutions", class.len())
+        .text("symmetry", &class.describe())
 }
 
-// For four indices between zero and 4, return the missing index.
-fn missing_index(ii: usize, jj: usize, kk: usize) -> usize {
-    assert!(ii < 4);
-    assert!(jj < 4);
-    assert!(kk < 4);
-    for ll in 0..4 {
-        if ll != ii && ll != jj && ll != kk {
-            return ll;
+fn solve_beermouth(report: &mut Report) {
+    let pool = LetterBag::from_word("beermouth").unwrap();
+    let words = report.read_words(Some(3));
+    let last_cands = match report.stats_format {
+        StatsFormat::Off => candidates(&words, &pool),
+        _ => counted_candidates(&words, &pool, &mut report.stats),
+    };
+    let grids: Vec<Vec<char>> = last_cands
+        .iter()
+        .map(|cand| presentation_format(cand).cells)
+        .collect();
+    let classes = classify(3, &grids, &Symmetry::all());
+    report
+        .stats
+        .record("symmetry classes", grids.len(), classes.len());
+    for last_cand in last_cands {
+        // Only print one grid out of each set of rotations and reflections.
+        let grid = presentation_format(&last_cand).cells;
+        let class = match classes.iter().find(|class| class.canonical == grid) {
+            Some(class) => class,
+            None => continue,
+        };
+        let mut caption = format!("Last candidate words ({}):\n", class.describe());
+        for word in last_cand.iter() {
+            let word_str: String = word.iter().collect();
+            caption += &format!("{} ", word_str);
         }
+        caption += "\nIn grid format:";
+        let mut drawing = Drawing::grid(&presentation_format(&last_cand));
+        if report.highlight {
+            drawing.highlight_readings(&GridSpec::beermouth().readings);
+        }
+        let words = last_cand.iter().map(|word| word.iter().collect::<String>());
+        let solution = class_solution(&drawing, class).list("words", words);
+        report.result(&caption, drawing, solution);
     }
-    unreachable!()
 }
 
-// Takes the candidates and finds words that make a proper word in the top row. The words are
-// returned such that the first word is the left to right diagonal (1, 5, 9 on a phone), the second
-// word is the middle vertical, the third is the right to left diagonal (3, 5, 7) and the remaining
-// word is last.
-fn top_row_candidates<'a>(
-    four_cands: &Vec<Vec<[char; 3]>>,
-    possible_words: &HashSet<[char; 3]>,
-) -> Vec<Vec<[char; 3]>> {
-    let mut out = Vec::new();
-    for cand in four_cands {
-        assert!(cand.len() == 4);
-        for ii in 0..cand.len() {
-            let first = cand[ii];
-            for jj in 0..cand.len() {
-                if jj == ii {
-                    continue;
-                }
-                let second = cand[jj];
-                for kk in 0..cand.len() {
-                    if kk == jj || kk == ii {
-                        continue;
-                    }
-                    let third = cand[kk];
-                    let word = [first[0], second[0], third[0]];
-                    if possible_words.contains(&word) {
-                        let mut top_cand = vec![first, second, third];
-                        top_cand.push(cand[missing_index(ii, jj, kk)]);
-                        out.push(top_cand);
-                    }
-                }
-            }
+// Runs the general solver on `spec`.
+fn solve_grid(spec: &GridSpec, skip_symmetric: bool, report: &mut Report) {
+    if spec.pattern.cells.iter().any(|&ch| ch != BLANK) {
+        report.picture("Starting from:", Drawing::grid(&spec.pattern));
+    }
+    let words = report.read_words(Some(spec.size));
+    let lexicon = Lexicon::new(words.iter().map(|s| s.as_str()), spec.size);
+    let (grids, group) = if skip_symmetric {
+        // Fill the skipped solutions back in so that they're counted.
+        let group = spec.symmetries();
+        let grids: Vec<Grid> = solve_distinct(spec, &lexicon)
+            .iter()
+            .flat_map(|grid| group.iter().map(move |&symmetry| grid.transform(symmetry)))
+            .collect();
+        (grids, group)
+    } else {
+        (solve(spec, &lexicon), Symmetry::all().to_vec())
+    };
+    report.stats.record("search", words.len(), grids.len());
+    let cells: Vec<Vec<char>> = grids.into_iter().map(|grid| grid.cells).collect();
+    let classes = classify(spec.size, &cells, &group);
+    report
+        .stats
+        .record("symmetry classes", cells.len(), classes.len());
+    let total: usize = classes.iter().map(|class| class.len()).sum();
+    report.note(&format!(
+        "Found {} grids, {} up to symmetry",
+        total,
+        classes.len()
+    ));
+    for class in classes {
+        let grid = Grid {
+            size: spec.size,
+            cells: class.canonical.clone(),
+        };
+        let mut drawing = Drawing::grid(&grid);
+        if report.highlight {
+            drawing.highlight_readings(&spec.readings);
         }
+        let solution = class_solution(&drawing, &class);
+        report.result(&format!("{}:", class.describe()), drawing, solution);
     }
-    out
 }
 
-// Filter out sets of four where the bottom generated from the top three words isn't a word.
-fn filter_by_bottom<'a>(cand: &Vec<[char; 3]>, possible_words: &HashSet<[char; 3]>) -> bool {
-    assert!(cand.len() == 4);
-    let first = cand[2][2];
-    let second = cand[1][2];
-    let third = cand[0][2];
-    let word = [first, second, third];
-    possible_words.contains(&word)
+// The command line option that adds `hint` to `spec`.
+fn hint_option(spec: &GridSpec, hint: &Hint) -> String {
+    match hint {
+        Hint::Require(word) => format!("--require {}", word),
+        Hint::Letter { .. } => {
+            let mut spec = spec.clone();
+            hint.apply(&mut spec);
+            format!("--pattern {}", spec.pattern)
+        }
+    }
 }
 
-fn filter_by_rest<'a>(cand: &Vec<[char; 3]>, possible_words: &HashSet<[char; 3]>) -> bool {
-    // The left vertical word is made up of the first letter of the first top word (left to
-    // right diagnol), the first letter of the remaining word, and the last letter of the
-    // third top word (right to left diagonal).
-    let left_v_word = [cand[0][0], cand[3][0], cand[2][2]];
-    if !possible_words.contains(&left_v_word) {
-        return false;
-    }
-    // The right vertical word is from the first letter of the third top word, the third letter
-    // of the remaining word and the third letter of the first top word.
-    let right_v_word = [cand[2][0], cand[3][2], cand[0][2]];
-    possible_words.contains(&right_v_word)
+// Reports whether `spec` has exactly one solution, and if it has more, the options that would
+// make it unique.
+fn check_grid(spec: &GridSpec, filter: &WordFilter, output: OutputFormat) {
+    let words = read_words(filter, Some(spec.size));
+    let lexicon = Lexicon::new(words.iter().map(|s| s.as_str()), spec.size);
+    let found = check_unique(spec, &lexicon);
+    let suggestions: Vec<String> = match &found {
+        Uniqueness::Ambiguous(first, second) => suggest(spec, &lexicon, first, second)
+            .iter()
+            .map(|hint| hint_option(spec, hint))
+            .collect(),
+        _ => Vec::new(),
+    };
+    uniqueness::report(&found, |grid| grid.to_string(), &suggestions, output).unwrap();
 }
 
-// Returns the 3x3 grid of letters in row major form.
-fn presentation_format(cand: &[[char; 3]]) -> Vec<Vec<char>> {
-    let mut out = Vec::new();
-    // Fill in the vectors with x's just as a placeholder.
-    for _ in 0..3 {
-        out.push(vec!['x', 'x', 'x']);
-    }
-    // The first word is the left to right diagonal.
-    out[0][0] = cand[0][0];
-    out[1][1] = cand[0][1];
-    out[2][2] = cand[0][2];
-    // The second word is the middle vertical.
-    out[0][1] = cand[1][0];
-    out[2][1] = cand[1][2];
-    // The third word is the left diagonal.
-    out[0][2] = cand[2][0];
-    out[2][0] = cand[2][2];
-    // The last word is the middle horizontal.
-    out[1][0] = cand[3][0];
-    out[1][2] = cand[3][2];
-    out
+// Lists the grids that come closest to solving `spec`.
+fn near_grids(
+    spec: &GridSpec,
+    filter: &WordFilter,
+    options: &NearOptions,
+    allowed: usize,
+    output: OutputFormat,
+) {
+    let words = read_words(filter, Some(spec.size));
+    let lexicon = Lexicon::new(words.iter().map(|s| s.as_str()), spec.size);
+    let misses = grid::near(spec, &lexicon, allowed);
+    near::report(misses, |grid| grid.to_string(), options.limit, output).unwrap();
 }
 
-fn main() {
-    let f = match File::open("dictionary.txt") {
-        Ok(f) => f,
-        Err(why) => panic!("Failed to open links.txt: {}", Error::description(&why)),
-    };
+// "rows, columns and diagonals" for --lines rows,columns,diagonals.
+fn describe_lines(lines: &str) -> String {
+    let names: Vec<&str> = lines.split(',').collect();
+    match names.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        _ => lines.to_string(),
+    }
+}
 
-    // Construct a vector of three letter words that are a subset of beermouth.
-    let source_word = construct_letter_map("beermouth");
-    let words: Vec<[char; 3]> = BufReader::new(f)
-        .lines()
-        .map(|s| s.unwrap())
-        .filter(|s| s.len() == 3)
-        .filter(|s| letter_map_less_than(&construct_letter_map(s), &source_word))
-        .map(|s| [get_char(&s, 0), get_char(&s, 1), get_char(&s, 2)])
+// Proposes up to `count` puzzles like `spec`, using common words as the letters, that have exactly
+// one answer among the common words.
+fn generate_grids(
+    spec: &GridSpec,
+    lines: &str,
+    options: &GenerateOptions,
+    count: usize,
+    output: OutputFormat,
+) -> Result<(), String> {
+    let common = options.load()?;
+    let words: Vec<&str> = common
+        .words()
+        .iter()
+        .map(|s| s.as_str())
+        .filter(|s| s.chars().count() == spec.size)
+        .collect();
+    let lexicon = Lexicon::new(words.iter().cloned(), spec.size);
+    let pools: Vec<&str> = common.words().iter().map(|s| s.as_str()).collect();
+    let mut given = String::new();
+    if spec.pattern.cells.iter().any(|&ch| ch != BLANK) {
+        given += &format!(", starting from {}", spec.pattern);
+    }
+    for word in &spec.required {
+        given += &format!(", with {} as one of them", word.to_uppercase());
+    }
+    let proposals = grid::generate(spec, &lexicon, &pools)
+        .into_iter()
+        .map(|(pool, grid, effort)| {
+            let words = grid.words(&spec.readings);
+            Proposal {
+                statement: format!(
+                    "Arrange the letters of {} in a {}x{} grid so that the {} all read as \
+                     {}-letter words{}.",
+                    pool.to_uppercase(),
+                    spec.size,
+                    spec.size,
+                    describe_lines(lines),
+                    spec.size,
+                    given
+                ),
+                answer: grid.to_string(),
+                rarest: common.rarest(words.iter().map(|s| s.as_str()).chain(Some(pool))),
+                effort,
+            }
+        })
         .collect();
+    generate::report(proposals, count, output).map_err(|why| why.to_string())
+}
 
-    // Find sets of words that share a middle letter.
-    let middle_candidates = shared_middle_letters(&words);
-    // Find sets of four words that share a middle letter and could make up the solution.
-    let four_cands = four_word_candidates(&middle_candidates, &source_word);
+// Prints up to `count` fills of `puzzle` along with the word in each slot.
+fn fill_crossword(puzzle: &Crossword, count: usize, report: &mut Report) {
+    report.picture("Filling:", Drawing::crossword(puzzle));
+    let words = report.read_words(None);
+    let fills = crossword::fill(puzzle, &words, count);
+    report.stats.record("fill", words.len(), fills.len());
+    if fills.is_empty() {
+        report.note("No fill found");
+    }
+    for filled in fills {
+        let mut drawing = Drawing::crossword(&filled);
+        drawing.highlight_slots(puzzle);
+        let answers = drawing
+            .highlights
+            .iter()
+            .map(|highlight| format!("{}: {}", highlight.label, drawing.word(highlight)));
+        let solution = Solution::new()
+            .list("rows", drawing.rows())
+            .list("answers", answers);
+        report.result("", drawing, solution);
+    }
+}
 
-    // Find possible top rows.
-    let word_set = {
-        let mut word_set = HashSet::with_capacity(words.len());
-        for word in words.iter() {
-            word_set.insert(word.clone());
+// Prints up to `count` word squares for `spec`.
+fn find_squares(spec: &SquareSpec, count: usize, report: &mut Report) {
+    let words = report.read_words(Some(spec.size));
+    let squares = squares::find(spec, &words, count);
+    report.stats.record("search", words.len(), squares.len());
+    if squares.is_empty() {
+        report.note("No square found");
+    }
+    let mut lines = "rows,columns".to_string();
+    if spec.diagonal {
+        lines += ",diagonal";
+    }
+    if spec.anti_diagonal {
+        lines += ",anti-diagonal";
+    }
+    let readings = parse_readings(spec.size, &lines).unwrap();
+    for square in squares {
+        let mut drawing = Drawing::grid(&square);
+        if report.highlight {
+            drawing.highlight_readings(&readings);
         }
-        word_set
-    };
-    let top_cands = top_row_candidates(&four_cands, &word_set);
-    let last_cands: Vec<&Vec<[char; 3]>> = top_cands
-        .iter()
-        .filter(|cand| filter_by_bottom(cand, &word_set))
-        .filter(|cand| filter_by_rest(cand, &word_set))
-        .collect();
+        let solution = Solution::new().list("rows", drawing.rows());
+        report.result("", drawing, solution);
+    }
+}
 
-    for last_cand in last_cands {
-        println!("Last candidate words:");
-        for word in last_cand.iter() {
-            let word_str: String = word.iter().collect();
-            print!("{} ", word_str);
+// Answers a letter bank `query` about `pool`.
+fn query_bank(
+    query: &str,
+    pool: LetterBag,
+    len: Option<usize>,
+    required: Option<char>,
+    min_len: usize,
+    report: &mut Report,
+) {
+    let dictionary = report.read_words(None);
+    let bank = Bank::new(&dictionary, pool);
+    let found = if let Some(writer) = &mut report.writer {
+        let solutions: Vec<Solution> = match query {
+            "words" => bank
+                .words(len, required)
+                .into_iter()
+                .map(|word| {
+                    Solution::new()
+                        .text("word", word)
+                        .number("length", word.len())
+                })
+                .collect(),
+            "partitions" => bank
+                .partitions(min_len)
+                .into_iter()
+                .map(|set| Solution::new().list("words", set))
+                .collect(),
+            "pairs" => bank
+                .pairs()
+                .into_iter()
+                .map(|(first, second)| Solution::new().text("first", first).text("second", second))
+                .collect(),
+            _ => unreachable!("parse_args checks the --bank query"),
+        };
+        for solution in &solutions {
+            writer.write(solution).unwrap();
         }
-        println!("\nIn grid format:");
-        let present = presentation_format(&last_cand);
-        for line in present {
-            for ch in line {
-                print!(" {} ", ch);
+        solutions.len()
+    } else {
+        match query {
+            "words" => {
+                let words = bank.words(len, required);
+                let mut current = 0;
+                for word in &words {
+                    if word.len() != current {
+                        current = word.len();
+                        println!("Length {}:", current);
+                    }
+                    println!("  {}", word);
+                }
+                words.len()
+            }
+            "partitions" => {
+                let sets = bank.partitions(min_len);
+                for set in &sets {
+                    println!("{}", set.join(" "));
+                }
+                sets.len()
             }
-            print!("\n");
+            "pairs" => {
+                let pairs = bank.pairs();
+                for (first, second) in &pairs {
+                    println!("{} {}", first, second);
+                }
+                pairs.len()
+            }
+            _ => unreachable!("parse_args checks the --bank query"),
         }
-        print!("\n");
-    }
+    };
+    report.stats.record(query, dictionary.len(), found);
+}
+
+struct Config {
+    grid: bool,
+    skip_symmetric: bool,
+    check: bool,
+    bank: Option<String>,
+    crossword: Option<Crossword>,
+    format: Format,
+    output: OutputFormat,
+    stats: StatsFormat,
+    generator: GenerateOptions,
+    near: NearOptions,
+    filter: WordFilter,
+    highlight: bool,
+    square: Option<SquareKind>,
+    diagonal: bool,
+    anti_diagonal: bool,
+    count: usize,
+    len: Option<usize>,
+    must_use: Option<char>,
+    min_len: usize,
+    size: usize,
+    // None until --letters is given, since the default depends on the mode. Some(None) is "any",
+    // which lifts the restriction.
+    letters: Option<Option<LetterBag>>,
+    lines: String,
+    pattern: Option<Grid>,
+    required: Vec<String>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_letter_map() {
-        let mut expected = HashMap::new();
-        expected.insert('b', 1);
-        expected.insert('e', 2);
-        expected.insert('r', 1);
-        expected.insert('m', 1);
-        expected.insert('o', 1);
-        expected.insert('u', 1);
-        expected.insert('t', 1);
-        expected.insert('h', 1);
-        assert_eq!(expected, construct_letter_map("beermouth"));
-    }
-
-    #[test]
-    fn test_map_less_than() {
-        let source_word = construct_letter_map("beermouth");
-        assert!(letter_map_less_than(
-            &construct_letter_map("bee"),
-            &source_word
-        ));
-        assert!(letter_map_less_than(
-            &construct_letter_map("bum"),
-            &source_word
-        ));
-        assert!(letter_map_less_than(
-            &construct_letter_map("but"),
-            &source_word
-        ));
-        assert!(letter_map_less_than(
-            &construct_letter_map("out"),
-            &source_word
-        ));
-        assert!(!letter_map_less_than(
-            &construct_letter_map("boo"),
-            &source_word
-        ));
-        assert!(!letter_map_less_than(
-            &construct_letter_map("oom"),
-            &source_word
-        ));
-        assert!(!letter_map_less_than(
-            &construct_letter_map("ouf"),
-            &source_word
-        ));
-    }
-
-    #[test]
-    fn test_shared_middle() {
-        let mut words = Vec::new();
-        words.push(['b', 'e', 'e']);
-        words.push(['b', 'e', 't']);
-        words.push(['m', 'e', 't']);
-        words.push(['b', 'u', 'm']);
-        words.push(['b', 'u', 't']);
-        words.push(['o', 'u', 't']);
-        words.push(['o', 'u', 'f']);
-        words.push(['b', 'o', 't']);
-        words.push(['h', 'o', 't']);
-        words.push(['m', 'o', 't']);
-        words.push(['t', 'o', 'm']);
-        words.push(['r', 'o', 't']);
-        let mut expected = Vec::new();
+fn parse_args() -> Result<Config, String> {
+    let mut config = Config {
+        grid: false,
+        skip_symmetric: false,
+        check: false,
+        bank: None,
+        crossword: None,
+        format: Format::Text,
+        output: OutputFormat::default(),
+        stats: StatsFormat::default(),
+        generator: GenerateOptions::default(),
+        near: NearOptions::default(),
+        filter: WordFilter::default(),
+        highlight: false,
+        square: None,
+        diagonal: false,
+        anti_diagonal: false,
+        count: 1,
+        len: None,
+        must_use: None,
+        min_len: 2,
+        size: 3,
+        letters: None,
+        lines: "rows,columns,diagonals".to_string(),
+        pattern: None,
+        required: Vec::new(),
+    };
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if config.output.parse_arg(&arg, &mut args)?
+            || config.stats.parse_arg(&arg, &mut args)?
+            || config.generator.parse_arg(&arg, &mut args)?
+            || config.near.parse_arg(&arg, &mut args)?
+            || config.filter.parse_arg(&arg, &mut args)?
         {
-            let mut group = Vec::new();
-            for ii in 3..7 {
-                group.push(words[ii]);
-            }
-            group.sort();
-            expected.push(group);
+            continue;
         }
-        {
-            let mut group = Vec::new();
-            for ii in 7..12 {
-                group.push(words[ii]);
+        match arg.as_str() {
+            "--grid" => config.grid = true,
+            "--skip-symmetric" => config.skip_symmetric = true,
+            "--check-unique" => config.check = true,
+            "--size" => config.size = flags::number(&arg, &mut args)?,
+            "--letters" => {
+                config.letters = Some(flags::named(&arg, &mut args, |letters| match letters {
+                    "any" => Some(None),
+                    letters => LetterBag::from_word(letters).map(Some),
+                })?)
+            }
+            "--lines" => config.lines = flags::value(&arg, &mut args)?,
+            "--bank" => {
+                config.bank = Some(flags::named(&arg, &mut args, |query| {
+                    ["words", "partitions", "pairs"]
+                        .contains(&query)
+                        .then(|| query.to_string())
+                })?)
+            }
+            "--crossword" => {
+                config.crossword = Some(flags::named(&arg, &mut args, Crossword::parse)?)
+            }
+            "--square" => config.square = Some(flags::named(&arg, &mut args, SquareKind::parse)?),
+            "--format" => config.format = flags::named(&arg, &mut args, Format::parse)?,
+            "--highlight" => config.highlight = true,
+            "--diagonal" => config.diagonal = true,
+            "--anti-diagonal" => config.anti_diagonal = true,
+            "--count" => config.count = flags::number(&arg, &mut args)?,
+            "--length" => config.len = Some(flags::number(&arg, &mut args)?),
+            "--must-use" => {
+                config.must_use = Some(flags::named(&arg, &mut args, |s| s.chars().next())?)
             }
-            group.sort();
-            expected.push(group);
+            "--pattern" => config.pattern = Some(flags::named(&arg, &mut args, Grid::parse)?),
+            "--require" => config.required.push(flags::value(&arg, &mut args)?),
+            "--min-len" => config.min_len = flags::number(&arg, &mut args)?,
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+    if config.square.is_some() && !(3..=7).contains(&config.size) {
+        return Err("Bad --size for --square, expected 3 to 7".to_string());
+    }
+    if config.bank.is_some() && config.letters == Some(None) {
+        return Err("--bank needs --letters from a-z".to_string());
+    }
+    if let Some(pattern) = &config.pattern {
+        config.size = pattern.size;
+    }
+    if parse_readings(config.size, &config.lines).is_none() {
+        return Err(format!("Unknown value for --lines: {}", config.lines));
+    }
+    Ok(config)
+}
+
+fn main() {
+    let config = match parse_args() {
+        Ok(config) => config,
+        Err(why) => {
+            eprintln!(
+                "{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}",
+                why,
+                USAGE,
+                UNIQUE_USAGE,
+                GENERATE_USAGE,
+                NEAR_USAGE,
+                FILTER_USAGE,
+                OUTPUT_USAGE,
+                STATS_USAGE
+            );
+            std::process::exit(1);
         }
-        let mut input_words = words.clone();
-        input_words.sort();
-        let mut shared = shared_middle_letters(&input_words);
-        // Sort by the lengths of the sub-Vecs to prevent test flakes.
-        shared.sort_by(|l, r| l.len().cmp(&r.len()));
-        assert_eq!(expected, shared);
+    };
+    let (format, output, stats, filter, highlight) = (
+        config.format,
+        config.output,
+        config.stats,
+        &config.filter,
+        config.highlight,
+    );
+
+    if let Some(kind) = config.square {
+        let spec = SquareSpec {
+            letters: config.letters.flatten(),
+            diagonal: config.diagonal,
+            anti_diagonal: config.anti_diagonal,
+            ..SquareSpec::new(config.size, kind)
+        };
+        let mut report = Report::new(format, output, stats, filter, highlight, "Word squares");
+        find_squares(&spec, config.count, &mut report);
+        report.finish();
+        return;
+    }
+    let beermouth = LetterBag::from_word("beermouth");
+    let pool = config.letters.unwrap_or(beermouth);
+    if let Some(query) = &config.bank {
+        // The answers are lists of words, so they're always printed as text. `parse_args` has
+        // already turned down --letters any.
+        let mut report = Report::new(Format::Text, output, stats, filter, false, "Letter bank");
+        let (len, must_use, min_len) = (config.len, config.must_use, config.min_len);
+        query_bank(query, pool.unwrap(), len, must_use, min_len, &mut report);
+        report.finish();
+        return;
+    }
+    if let Some(puzzle) = &config.crossword {
+        let mut report = Report::new(format, output, stats, filter, highlight, "Crossword fills");
+        fill_crossword(puzzle, config.count, &mut report);
+        report.finish();
+        return;
+    }
+    let general = config.generator.count.is_some() || config.near.allowed.is_some();
+    if !config.grid
+        && !config.check
+        && !general
+        && config.pattern.is_none()
+        && config.required.is_empty()
+    {
+        let mut report = Report::new(
+            format,
+            output,
+            stats,
+            filter,
+            highlight,
+            "Oct 21, 2018 puzzle",
+        );
+        solve_beermouth(&mut report);
+        report.finish();
+        return;
+    }
+    let readings = parse_readings(config.size, &config.lines).unwrap();
+    let mut spec = GridSpec::new(config.size, readings, pool);
+    if let Some(pattern) = config.pattern {
+        spec.pattern = pattern;
+    }
+    spec.required = config.required;
+    if let Some(count) = config.generator.count {
+        if let Err(why) = generate_grids(&spec, &config.lines, &config.generator, count, output) {
+            eprintln!("{}", why);
+            std::process::exit(1);
+        }
+        return;
+    }
+    if let Some(allowed) = config.near.allowed {
+        near_grids(&spec, filter, &config.near, allowed, output);
+        return;
+    }
+    if config.check {
+        check_grid(&spec, filter, output);
+        return;
     }
+    let mut report = Report::new(format, output, stats, filter, highlight, "Word grids");
+    solve_grid(&spec, config.skip_symmetric, &mut report);
+    report.finish();
 }
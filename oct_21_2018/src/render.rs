@@ -0,0 +1,344 @@
+// Drawing solved (or partly solved) grids for people: the spaced out letters the solvers have
+// always printed, Unicode box art for pasting into chat, and standalone SVG or an HTML page for
+// the wiki. The lines that had to be words can be highlighted, each in its own color with a
+// legend of the words they spell.
+
+use crossword::{Crossword, BLACK};
+use grid::{Grid, Reading, BLANK};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Boxes,
+    Svg,
+    Html,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "boxes" => Some(Format::Boxes),
+            "svg" => Some(Format::Svg),
+            "html" => Some(Format::Html),
+            _ => None,
+        }
+    }
+}
+
+// A line of cells to highlight, such as a row of a grid or a slot of a crossword.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Highlight {
+    pub label: String,
+    // Row major indices in reading order.
+    pub cells: Vec<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Drawing {
+    pub width: usize,
+    pub height: usize,
+    // Row major: `BLACK`, `BLANK` or a letter.
+    pub cells: Vec<char>,
+    // The clue number printed in the corner of each cell, if any.
+    pub numbers: Vec<Option<usize>>,
+    pub highlights: Vec<Highlight>,
+}
+
+// Colors for the highlights, reused in order when there are more lines than colors.
+const PALETTE: &[&str] = &[
+    "#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4", "#42d4f4", "#f032e6", "#9a6324",
+];
+
+// The width and height of a cell in SVG units.
+const CELL: usize = 40;
+
+impl Drawing {
+    pub fn grid(grid: &Grid) -> Drawing {
+        Drawing {
+            width: grid.size,
+            height: grid.size,
+            cells: grid.cells.clone(),
+            numbers: vec![None; grid.cells.len()],
+            highlights: Vec::new(),
+        }
+    }
+
+    // A crossword with its clue numbers.
+    pub fn crossword(puzzle: &Crossword) -> Drawing {
+        let mut numbers = vec![None; puzzle.cells.len()];
+        for slot in puzzle.slots() {
+            numbers[slot.cells[0]] = Some(slot.number);
+        }
+        Drawing {
+            width: puzzle.width,
+            height: puzzle.height,
+            cells: puzzle.cells.clone(),
+            numbers,
+            highlights: Vec::new(),
+        }
+    }
+
+    pub fn highlight_readings(&mut self, readings: &[Reading]) {
+        for reading in readings {
+            self.highlights.push(Highlight {
+                label: reading.name(),
+                cells: reading.cells(self.width),
+            });
+        }
+    }
+
+    // Highlights every slot of `puzzle`, labelled like "1 across".
+    pub fn highlight_slots(&mut self, puzzle: &Crossword) {
+        for slot in puzzle.slots() {
+            self.highlights.push(Highlight {
+                label: format!("{} {}", slot.number, slot.direction.name()),
+                cells: slot.cells,
+            });
+        }
+    }
+
+    pub fn word(&self, highlight: &Highlight) -> String {
+        highlight
+            .cells
+            .iter()
+            .map(|&cell| self.cells[cell])
+            .collect()
+    }
+
+    pub fn rows(&self) -> Vec<String> {
+        self.cells
+            .chunks(self.width)
+            .map(|row| row.iter().collect())
+            .collect()
+    }
+
+    // One "label: word" line per highlight.
+    pub fn legend(&self) -> String {
+        let mut out = String::new();
+        for highlight in &self.highlights {
+            out += &format!("  {}: {}\n", highlight.label, self.word(highlight));
+        }
+        out
+    }
+
+    // The letters spaced out one row per line, as the solvers print them.
+    pub fn text(&self) -> String {
+        let mut out = String::new();
+        for row in self.cells.chunks(self.width) {
+            for ch in row {
+                out += &format!(" {} ", ch);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    // The grid drawn with box drawing characters. Black squares are filled in and blanks are
+    // left empty.
+    pub fn boxes(&self) -> String {
+        let border = |left: char, middle: char, right: char| -> String {
+            let mut line = left.to_string();
+            for col in 0..self.width {
+                line += "───";
+                line.push(if col + 1 == self.width { right } else { middle });
+            }
+            line.push('\n');
+            line
+        };
+        let mut out = border('┌', '┬', '┐');
+        for (row, cells) in self.cells.chunks(self.width).enumerate() {
+            if row > 0 {
+                out += &border('├', '┼', '┤');
+            }
+            out.push('│');
+            for &ch in cells {
+                out += &match ch {
+                    BLACK => "███".to_string(),
+                    BLANK => "   ".to_string(),
+                    ch => format!(" {} ", ch),
+                };
+                out.push('│');
+            }
+            out.push('\n');
+        }
+        out += &border('└', '┴', '┘');
+        out
+    }
+
+    // A standalone SVG image. Highlights are drawn as wide translucent strokes through the
+    // centers of their cells, under the letters.
+    pub fn svg(&self) -> String {
+        let (width, height) = (self.width * CELL + 4, self.height * CELL + 4);
+        let corner = |cell: usize| (2 + cell % self.width * CELL, 2 + cell / self.width * CELL);
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             viewBox=\"0 0 {} {}\" font-family=\"sans-serif\">\n",
+            width, height, width, height
+        );
+        for (cell, &ch) in self.cells.iter().enumerate() {
+            let (x, y) = corner(cell);
+            out += &format!(
+                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" \
+                 stroke=\"black\" stroke-width=\"2\"/>\n",
+                x,
+                y,
+                CELL,
+                CELL,
+                if ch == BLACK { "black" } else { "white" }
+            );
+        }
+        for (ii, highlight) in self.highlights.iter().enumerate() {
+            let (first, last) = match (highlight.cells.first(), highlight.cells.last()) {
+                (Some(&first), Some(&last)) => (corner(first), corner(last)),
+                _ => continue,
+            };
+            out += &format!(
+                "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" \
+                 stroke-width=\"14\" stroke-linecap=\"round\" stroke-opacity=\"0.4\">\
+                 <title>{}: {}</title></line>\n",
+                first.0 + CELL / 2,
+                first.1 + CELL / 2,
+                last.0 + CELL / 2,
+                last.1 + CELL / 2,
+                PALETTE[ii % PALETTE.len()],
+                escape(&highlight.label),
+                escape(&self.word(highlight))
+            );
+        }
+        for (cell, number) in self.numbers.iter().enumerate() {
+            if let Some(number) = number {
+                let (x, y) = corner(cell);
+                out += &format!(
+                    "  <text x=\"{}\" y=\"{}\" font-size=\"10\">{}</text>\n",
+                    x + 3,
+                    y + 11,
+                    number
+                );
+            }
+        }
+        for (cell, &ch) in self.cells.iter().enumerate() {
+            if ch == BLACK || ch == BLANK {
+                continue;
+            }
+            let (x, y) = corner(cell);
+            out += &format!(
+                "  <text x=\"{}\" y=\"{}\" font-size=\"24\" text-anchor=\"middle\">{}</text>\n",
+                x + CELL / 2,
+                y + CELL / 2 + 8,
+                escape(&ch.to_uppercase().to_string())
+            );
+        }
+        out += "</svg>\n";
+        out
+    }
+}
+
+// Escapes text for use in HTML or SVG.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// A standalone HTML page with a paragraph for each note, then each drawing with its caption and,
+// if it has highlights, a legend in the matching colors.
+pub fn html(title: &str, notes: &[String], drawings: &[(String, Drawing)]) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>\n  body {{ font-family: sans-serif; }}\n  \
+         figure {{ display: inline-block; margin: 1em; vertical-align: top; }}\n  \
+         ul {{ list-style: none; padding: 0; }}\n</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        escape(title),
+        escape(title)
+    );
+    for note in notes {
+        out += &format!("<p>{}</p>\n", escape(note));
+    }
+    for (caption, drawing) in drawings {
+        out += "<figure>\n";
+        out += &drawing.svg();
+        if !caption.is_empty() {
+            out += &format!(
+                "<figcaption>{}</figcaption>\n",
+                escape(caption).replace('\n', "<br>")
+            );
+        }
+        if !drawing.highlights.is_empty() {
+            out += "<ul>\n";
+            for (ii, highlight) in drawing.highlights.iter().enumerate() {
+                out += &format!(
+                    "  <li><span style=\"color: {}\">&#9632;</span> {}: {}</li>\n",
+                    PALETTE[ii % PALETTE.len()],
+                    escape(&highlight.label),
+                    escape(&drawing.word(highlight))
+                );
+            }
+            out += "</ul>\n";
+        }
+        out += "</figure>\n";
+    }
+    out += "</body>\n</html>\n";
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::parse_readings;
+
+    fn drawing(rows: &[&str]) -> Drawing {
+        let rows: Vec<Vec<char>> = rows.iter().map(|row| row.chars().collect()).collect();
+        Drawing::grid(&Grid::from_rows(&rows))
+    }
+
+    #[test]
+    fn test_boxes() {
+        let drawing = Drawing::crossword(&Crossword::parse("a#/.b").unwrap());
+        assert_eq!(
+            drawing.boxes(),
+            "┌───┬───┐\n│ a │███│\n├───┼───┤\n│   │ b │\n└───┴───┘\n"
+        );
+        assert_eq!(drawing.text(), " a  # \n .  b \n");
+        assert_eq!(drawing.rows(), vec!["a#", ".b"]);
+    }
+
+    #[test]
+    fn test_legend() {
+        let mut drawing = drawing(&["hem", "obu", "ter"]);
+        drawing.highlight_readings(&parse_readings(3, "row-0,reversed-column-2").unwrap());
+        assert_eq!(drawing.legend(), "  row-0: hem\n  reversed-column-2: rum\n");
+
+        let puzzle = Crossword::parse("ab#/cde/#fg").unwrap();
+        let mut drawing = Drawing::crossword(&puzzle);
+        drawing.highlight_slots(&puzzle);
+        assert_eq!(drawing.numbers[0], Some(1));
+        assert_eq!(drawing.numbers[5], Some(4));
+        assert!(drawing
+            .legend()
+            .starts_with("  1 across: ab\n  1 down: ac\n"));
+    }
+
+    #[test]
+    fn test_svg() {
+        let mut drawing = drawing(&["ab", "cd"]);
+        drawing.highlight_readings(&parse_readings(2, "rows").unwrap());
+        let svg = drawing.svg();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"84\""));
+        assert_eq!(svg.matches("<rect ").count(), 4);
+        assert_eq!(svg.matches("<line ").count(), 2);
+        assert!(svg.contains("<title>row-1: cd</title>"));
+        assert!(svg.contains(">D</text>"));
+
+        let page = html(
+            "Q & A",
+            &["Found 2 grids".to_string()],
+            &[("2 solutions".to_string(), drawing)],
+        );
+        assert!(page.contains("<p>Found 2 grids</p>"));
+        assert!(page.contains("<title>Q &amp; A</title>"));
+        assert!(page.contains("<figcaption>2 solutions</figcaption>"));
+        assert_eq!(page.matches("<li>").count(), 2);
+    }
+}
@@ -0,0 +1,246 @@
+// Word squares: every row of the grid is a word and so is every column. In a classic square the
+// columns are the same words as the rows ("bit/ice/ten"), in a double square all of the words are
+// different. Cells are filled in row major order while following a trie of the words along each
+// row and column (and the diagonals if they count) at once, so a letter is only tried if every
+// line through the cell can still become a word.
+
+use grid::Grid;
+use std::collections::HashSet;
+use word_tools::letters::LetterBag;
+use word_tools::trie::Trie;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SquareKind {
+    Classic,
+    Double,
+}
+
+impl SquareKind {
+    pub fn parse(name: &str) -> Option<SquareKind> {
+        match name {
+            "classic" => Some(SquareKind::Classic),
+            "double" => Some(SquareKind::Double),
+            _ => None,
+        }
+    }
+}
+
+pub struct SquareSpec {
+    pub size: usize,
+    pub kind: SquareKind,
+    // The letters that may be used, as in `GridSpec`. None allows any letter.
+    pub letters: Option<LetterBag>,
+    // Whether the diagonal (top left to bottom right) has to be a word too.
+    pub diagonal: bool,
+    // Whether the anti-diagonal (top right to bottom left) has to be a word too.
+    pub anti_diagonal: bool,
+}
+
+impl SquareSpec {
+    pub fn new(size: usize, kind: SquareKind) -> SquareSpec {
+        SquareSpec {
+            size,
+            kind,
+            letters: None,
+            diagonal: false,
+            anti_diagonal: false,
+        }
+    }
+}
+
+struct Search<'a> {
+    spec: &'a SquareSpec,
+    trie: &'a Trie,
+    cells: Vec<char>,
+    // The trie node each row, column and diagonal has reached so far.
+    rows: Vec<usize>,
+    columns: Vec<usize>,
+    diagonal: usize,
+    anti_diagonal: usize,
+    pool: Option<LetterBag>,
+    limit: usize,
+    out: Vec<Grid>,
+}
+
+impl Search<'_> {
+    // Whether a finished grid should be reported. A double square has to use 2N different words,
+    // and its transpose is a double square too, so only the one whose first row comes before its
+    // first column is kept. That doesn't hold when the anti-diagonal counts, since transposing
+    // reverses it.
+    fn accept(&self) -> bool {
+        if self.spec.kind == SquareKind::Classic {
+            return true;
+        }
+        let grid = Grid {
+            size: self.spec.size,
+            cells: self.cells.clone(),
+        };
+        let rows: Vec<String> = grid.rows().iter().map(|row| row.iter().collect()).collect();
+        let columns: Vec<String> = (0..self.spec.size)
+            .map(|col| {
+                (0..self.spec.size)
+                    .map(|row| grid.cells[row * grid.size + col])
+                    .collect()
+            })
+            .collect();
+        let distinct: HashSet<&String> = rows.iter().chain(columns.iter()).collect();
+        distinct.len() == 2 * self.spec.size && (self.spec.anti_diagonal || rows[0] < columns[0])
+    }
+
+    fn fill(&mut self, cell: usize) {
+        if self.out.len() >= self.limit {
+            return;
+        }
+        let size = self.spec.size;
+        if cell == size * size {
+            if self.accept() {
+                self.out.push(Grid {
+                    size,
+                    cells: self.cells.clone(),
+                });
+            }
+            return;
+        }
+        let (row, col) = (cell / size, cell % size);
+        let on_diagonal = self.spec.diagonal && row == col;
+        let on_anti_diagonal = self.spec.anti_diagonal && row + col == size - 1;
+        // Below the diagonal of a classic square the letter is already set by its mirror image.
+        let mirrored = if self.spec.kind == SquareKind::Classic && col < row {
+            Some(self.cells[col * size + row])
+        } else {
+            None
+        };
+        let trie = self.trie;
+        for (letter, next_row) in trie.children(self.rows[row]) {
+            if mirrored.is_some_and(|mirrored| mirrored != letter) {
+                continue;
+            }
+            let next_column = match trie.child(self.columns[col], letter) {
+                Some(node) => node,
+                None => continue,
+            };
+            if (col + 1 == size && !trie.is_word(next_row))
+                || (row + 1 == size && !trie.is_word(next_column))
+            {
+                continue;
+            }
+            let next_diagonal = if on_diagonal {
+                match trie.child(self.diagonal, letter) {
+                    Some(node) => node,
+                    None => continue,
+                }
+            } else {
+                self.diagonal
+            };
+            let next_anti_diagonal = if on_anti_diagonal {
+                match trie.child(self.anti_diagonal, letter) {
+                    Some(node) => node,
+                    None => continue,
+                }
+            } else {
+                self.anti_diagonal
+            };
+            if let Some(pool) = &mut self.pool {
+                if !pool.remove(letter) {
+                    continue;
+                }
+            }
+            let saved = (
+                self.rows[row],
+                self.columns[col],
+                self.diagonal,
+                self.anti_diagonal,
+            );
+            self.rows[row] = next_row;
+            self.columns[col] = next_column;
+            self.diagonal = next_diagonal;
+            self.anti_diagonal = next_anti_diagonal;
+            self.cells[cell] = letter;
+            self.fill(cell + 1);
+            self.rows[row] = saved.0;
+            self.columns[col] = saved.1;
+            self.diagonal = saved.2;
+            self.anti_diagonal = saved.3;
+            if let Some(pool) = &mut self.pool {
+                pool.insert(letter);
+            }
+        }
+    }
+}
+
+// Finds up to `limit` squares for `spec` using the `spec.size` letter words of `dictionary`, in
+// alphabetical order of their rows.
+pub fn find(spec: &SquareSpec, dictionary: &[String], limit: usize) -> Vec<Grid> {
+    let trie = Trie::from_words(
+        dictionary
+            .iter()
+            .filter(|word| word.len() == spec.size)
+            .map(|word| word.as_str()),
+    );
+    let mut search = Search {
+        spec,
+        trie: &trie,
+        cells: vec![' '; spec.size * spec.size],
+        rows: vec![Trie::ROOT; spec.size],
+        columns: vec![Trie::ROOT; spec.size],
+        diagonal: Trie::ROOT,
+        anti_diagonal: Trie::ROOT,
+        pool: spec.letters,
+        limit,
+        out: Vec::new(),
+    };
+    search.fill(0);
+    search.out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary(words: &[&str]) -> Vec<String> {
+        words.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn grid(rows: &[&str]) -> Grid {
+        let rows: Vec<Vec<char>> = rows.iter().map(|row| row.chars().collect()).collect();
+        Grid::from_rows(&rows)
+    }
+
+    #[test]
+    fn test_classic() {
+        let words = dictionary(&["bit", "ice", "ten", "bat", "tan", "cat", "bite"]);
+        let spec = SquareSpec::new(3, SquareKind::Classic);
+        assert_eq!(find(&spec, &words, 10), vec![grid(&["bit", "ice", "ten"])]);
+        // Its rows are its columns.
+        let spec = SquareSpec::new(3, SquareKind::Double);
+        assert!(find(&spec, &words, 10).is_empty());
+    }
+
+    #[test]
+    fn test_double() {
+        let words = dictionary(&["abc", "def", "ghi", "adg", "beh", "cfi", "aei", "ceg"]);
+        let mut spec = SquareSpec::new(3, SquareKind::Double);
+        // The transpose "adg/beh/cfi" is left out.
+        assert_eq!(find(&spec, &words, 10), vec![grid(&["abc", "def", "ghi"])]);
+        spec.diagonal = true;
+        assert_eq!(find(&spec, &words, 10).len(), 1);
+        // Read the other way the anti-diagonal is "gec", so only one of the two works.
+        spec.anti_diagonal = true;
+        assert_eq!(find(&spec, &words, 10), vec![grid(&["abc", "def", "ghi"])]);
+        spec.letters = LetterBag::from_word("abcdefghz");
+        assert!(find(&spec, &words, 10).is_empty());
+        spec.letters = LetterBag::from_word("ihgfedcba");
+        assert_eq!(find(&spec, &words, 10).len(), 1);
+    }
+
+    #[test]
+    fn test_limit() {
+        let words = dictionary(&["aa", "ab", "ba", "bb"]);
+        let spec = SquareSpec::new(2, SquareKind::Classic);
+        assert_eq!(find(&spec, &words, 10).len(), 8);
+        assert_eq!(
+            find(&spec, &words, 2),
+            vec![grid(&["aa", "aa"]), grid(&["aa", "ab"])]
+        );
+    }
+}
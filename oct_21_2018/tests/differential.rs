@@ -0,0 +1,163 @@
+// Runs the constructive solver, the general backtracking solver and Pat Rondon's brute force on
+// random letter pools and small random dictionaries, and reports every case where they disagree.
+// The brute force tries every arrangement, so it's the reference for the other two. The
+// constructive solver picks four different words for the lines through the center, so it's only
+// held to the reference grids where those lines are four different words.
+
+extern crate oct_21_2018;
+extern crate oct_21_2018_pat_rondon;
+extern crate word_tools;
+
+use oct_21_2018::beermouth;
+use oct_21_2018::grid::{self, GridSpec, Lexicon};
+use std::collections::HashSet;
+use word_tools::letters::LetterBag;
+
+// A small xorshift generator so that failures are reproducible from the seed.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+
+    fn word(&mut self, alphabet: &[char], len: usize) -> String {
+        (0..len)
+            .map(|_| alphabet[self.below(alphabet.len())])
+            .collect()
+    }
+}
+
+struct Case {
+    pool: String,
+    dictionary: Vec<String>,
+}
+
+// Few distinct letters make repeated letters and words likely, which is where the solvers' shortcuts
+// are most likely to go wrong. Half the cases have a solution planted in them.
+fn random_case(rng: &mut Rng) -> Case {
+    let alphabet: Vec<char> = "abcdefg"[..3 + rng.below(5)].chars().collect();
+    let pool = rng.word(&alphabet, 9);
+    let mut dictionary: Vec<String> = (0..10 + rng.below(30))
+        .map(|_| rng.word(&alphabet, 3))
+        .collect();
+    if rng.below(2) == 0 {
+        let mut cells: Vec<char> = pool.chars().collect();
+        for ii in (1..cells.len()).rev() {
+            cells.swap(ii, rng.below(ii + 1));
+        }
+        let line =
+            |indices: [usize; 3]| -> String { indices.iter().map(|&ii| cells[ii]).collect() };
+        for indices in [
+            [0, 1, 2],
+            [3, 4, 5],
+            [6, 7, 8],
+            [0, 3, 6],
+            [1, 4, 7],
+            [2, 5, 8],
+            [0, 4, 8],
+            [2, 4, 6],
+        ] {
+            dictionary.push(line(indices));
+        }
+    }
+    Case { pool, dictionary }
+}
+
+// Whether the diagonals and middle lines of the 3x3 `rows` are four different words.
+fn distinct_center_words(rows: &[Vec<char>]) -> bool {
+    let lines: HashSet<[char; 3]> = [
+        [rows[0][0], rows[1][1], rows[2][2]],
+        [rows[0][1], rows[1][1], rows[2][1]],
+        [rows[0][2], rows[1][1], rows[2][0]],
+        [rows[1][0], rows[1][1], rows[1][2]],
+    ]
+    .iter()
+    .cloned()
+    .collect();
+    lines.len() == 4
+}
+
+// Describes how the solvers' answers to `case` differ, if they do.
+fn disagreement(case: &Case) -> Option<String> {
+    let pool = LetterBag::from_word(&case.pool).unwrap();
+    let words: HashSet<String> = case.dictionary.iter().cloned().collect();
+    let reference = oct_21_2018_pat_rondon::solve(&case.pool, &words);
+
+    // The constructive solver expects each word once, as in dictionary.txt.
+    let mut unique: Vec<String> = words.iter().cloned().collect();
+    unique.sort();
+    let constructive = beermouth::solve(&unique, &pool);
+    let spec = GridSpec {
+        letters: Some(pool),
+        ..GridSpec::beermouth()
+    };
+    let lexicon = Lexicon::new(case.dictionary.iter().map(|s| s.as_str()), 3);
+    let general: Vec<Vec<Vec<char>>> = grid::solve(&spec, &lexicon)
+        .iter()
+        .map(|grid| grid.rows())
+        .collect();
+
+    let show = |grids: &[Vec<Vec<char>>]| -> String {
+        let grids: Vec<String> = grids
+            .iter()
+            .map(|rows| {
+                let rows: Vec<String> = rows.iter().map(|row| row.iter().collect()).collect();
+                rows.join("/")
+            })
+            .collect();
+        format!("[{}]", grids.join(" "))
+    };
+    let distinct: Vec<Vec<Vec<char>>> = reference
+        .iter()
+        .filter(|rows| distinct_center_words(rows))
+        .cloned()
+        .collect();
+    let mut out = Vec::new();
+    for (name, found, reference) in [
+        ("constructive", &constructive, &distinct),
+        ("general", &general, &reference),
+    ] {
+        if found != reference {
+            out.push(format!(
+                "  {} found {}, brute force found {}",
+                name,
+                show(found),
+                show(reference)
+            ));
+        }
+    }
+    if out.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "pool {} with {:?}:\n{}",
+            case.pool,
+            case.dictionary,
+            out.join("\n")
+        ))
+    }
+}
+
+#[test]
+fn test_solvers_agree() {
+    let mut rng = Rng(0x2018_1021);
+    let mut solved = 0;
+    let mut failures = Vec::new();
+    for _ in 0..300 {
+        let case = random_case(&mut rng);
+        if let Some(why) = disagreement(&case) {
+            failures.push(why);
+        }
+        let words: HashSet<String> = case.dictionary.iter().cloned().collect();
+        if !oct_21_2018_pat_rondon::solve(&case.pool, &words).is_empty() {
+            solved += 1;
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+    // Make sure the cases aren't all trivially empty.
+    assert!(solved > 100, "only {} cases had solutions", solved);
+}
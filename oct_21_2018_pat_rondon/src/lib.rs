@@ -0,0 +1,86 @@
+// Pat Rondon's brute force solution to the Oct 21, 2018 puzzle as a library, so that it can be
+// checked against the constructive solver in oct_21_2018.
+
+use std::collections::HashSet;
+
+pub mod permutations;
+use permutations::MultisetPermutations;
+
+fn is_word<'a, I>(letters: I, words: &HashSet<String>) -> bool
+where
+    I: Iterator<Item = &'a char>,
+{
+    let word: String = letters.collect();
+    words.contains(&word)
+}
+
+// Finds every arrangement of `letters` into a square grid where every row, column and diagonal is
+// one of `words`. Returns each grid once, as rows, sorted.
+pub fn solve(letters: &str, words: &HashSet<String>) -> Vec<Vec<Vec<char>>> {
+    let letters: Vec<char> = letters.chars().collect();
+    let size = (letters.len() as f64).sqrt() as usize;
+    if size == 0 || size * size != letters.len() {
+        panic!("{} letters don't make a square grid", letters.len());
+    }
+
+    let mut solutions = Vec::new();
+    let mut perms = MultisetPermutations::new(letters);
+    while let Some(perm) = perms.next() {
+        // The rows are filled first, so a row that isn't a word rules out every arrangement that
+        // shares the letters up to the end of it.
+        let bad_row = perm
+            .chunks(size)
+            .position(|row| !is_word(row.iter(), words));
+        if let Some(row) = bad_row {
+            perms.skip_prefix((row + 1) * size);
+            continue;
+        }
+        let missing = (0..size).any(|col| !is_word(perm[col..].iter().step_by(size), words));
+        if missing {
+            continue;
+        }
+
+        let diagonal_top_left = (0..size).map(|ii| &perm[ii * size + ii]);
+        if !is_word(diagonal_top_left, words) {
+            continue;
+        }
+
+        let diagonal_top_right = (0..size).map(|ii| &perm[ii * size + size - 1 - ii]);
+        if !is_word(diagonal_top_right, words) {
+            continue;
+        }
+
+        solutions.push(perm.chunks(size).map(|row| row.to_vec()).collect());
+    }
+    // The permutations come out in order, so the solutions already are.
+    solutions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let words: HashSet<String> = [
+            "hem", "obu", "ter", "hot", "ebe", "mur", "hbr", "mbt", "tbm",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let rows = |grid: &[&str]| -> Vec<Vec<char>> {
+            grid.iter().map(|row| row.chars().collect()).collect()
+        };
+        assert_eq!(
+            solve("beermouth", &words),
+            vec![rows(&["hem", "obu", "ter"]), rows(&["hot", "ebe", "mur"])]
+        );
+        assert!(solve("abcdefghi", &words).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "0 letters don't make a square grid")]
+    fn test_solve_no_letters() {
+        solve("", &HashSet::new());
+    }
+}
@@ -5,83 +5,95 @@
     Given the letters in the word 'beermouth', contruct a three by three matrix of words where all
     the verticals, horizontals, and diagonals form three letter words.
 
-  This solution is a (nearly line for line) translation of the solution written y Pat Rondon here:
+  This solution started as a (nearly line for line) translation of the solution written y Pat
+  Rondon here:
   https://gist.github.com/pat-rondon/9568e2840d9af31030bf4c9d4b7ebae8
+
+  It still tries every arrangement of the letters, but only the distinct ones (the repeated 'e'
+  made Heap's algorithm find every solution twice), and it skips every arrangement that starts with
+  a row that isn't a word. That makes 4x4 grids feasible: pass the letters as the only argument
+  and the grid size is the square root of their count. Solutions that are rotations or reflections
+  of each other are printed once. --output-format jsonl or csv prints them for scripts instead.
+  --lowercase-only and --reject LIST filter the dictionary like the other solvers do, and
+  --stats table or json prints how many words and grids each stage kept to stderr.
 */
 
 use std::collections::HashSet;
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::iter::Iterator;
-
-extern crate permutohedron;
-use permutohedron::Heap;
+use std::env;
 
-extern crate itertools;
-use itertools::any;
-use itertools::Itertools;
-
-fn is_word<'a, I>(letters: I, words: &HashSet<String>) -> bool
-where
-    I: Iterator<Item = &'a char>,
-{
-    let word: String = letters.collect();
-    words.contains(&word)
-}
+extern crate oct_21_2018_pat_rondon;
+extern crate word_tools;
+use word_tools::dictionary::{load_words, WordFilter};
+use word_tools::solution::{stdout_writer, OutputFormat, Solution};
+use word_tools::stats::{Stats, StatsFormat};
+use word_tools::symmetry::{classify, Symmetry};
 
 fn main() {
-    let f = match File::open("dictionary.txt") {
-        Ok(f) => f,
-        Err(why) => panic!("Failed to open links.txt: {}", Error::description(&why)),
-    };
-
-    let words: HashSet<String> = BufReader::new(f)
-        .lines()
-        .map(|s| s.unwrap())
-        .filter(|s| s.len() == 3)
-        .collect();
-
-    let mut letters: Vec<char> = "beermouth".chars().collect();
-    let heap = Heap::new(&mut letters);
-
-    for perm in heap {
-        let rows = perm.iter().chunks(3);
-        if any(&rows, |letters| !is_word(letters, &words)) {
-            continue;
-        }
-        let mut missing = false;
-        let mut iter_perm = perm.iter();
-        for _ in 0..3 {
-            let word: String = iter_perm.clone().step_by(3).collect();
-            if !words.contains(&word) {
-                missing = true;
-                break;
+    let mut letters = "beermouth".to_string();
+    let mut filter = WordFilter::default();
+    let mut output = OutputFormat::default();
+    let mut stats_format = StatsFormat::default();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let found = filter
+            .parse_arg(&arg, &mut args)
+            .and_then(|found| Ok(found || output.parse_arg(&arg, &mut args)?))
+            .and_then(|found| Ok(found || stats_format.parse_arg(&arg, &mut args)?));
+        match found {
+            Ok(true) => continue,
+            Ok(false) => (),
+            Err(why) => {
+                eprintln!("{}", why);
+                std::process::exit(1);
             }
-            iter_perm.next();
         }
-        if missing {
-            continue;
+        if arg.starts_with('-') {
+            eprintln!("Unknown argument: {}", arg);
+            std::process::exit(1);
         }
+        letters = arg;
+    }
+    // `solve` asserts this, so catch it here with a message instead.
+    let count = letters.chars().count();
+    let size = (count as f64).sqrt() as usize;
+    if size == 0 || size * size != count {
+        eprintln!("{} letters don't make a square grid", count);
+        std::process::exit(1);
+    }
 
-        let diagonal_top_left: String = [perm[0], perm[4], perm[8]].iter().collect();
-        if !words.contains(&diagonal_top_left) {
-            continue;
-        }
+    let mut stats = Stats::new();
+    let words: HashSet<String> = load_words("dictionary.txt", &filter)
+        .into_iter()
+        .filter(|s| s.chars().count() == size)
+        .collect();
+    stats.record("dictionary", words.len(), words.len());
 
-        let diagonal_top_right: String = [perm[2], perm[4], perm[6]].iter().collect();
-        if !words.contains(&diagonal_top_right) {
+    let solutions: Vec<Vec<char>> = oct_21_2018_pat_rondon::solve(&letters, &words)
+        .into_iter()
+        .map(|rows| rows.concat())
+        .collect();
+    stats.record("arrangements", words.len(), solutions.len());
+    let classes = classify(size, &solutions, &Symmetry::all());
+    stats.record("symmetry", solutions.len(), classes.len());
+    let mut writer = stdout_writer(output);
+    for class in classes {
+        if let Some(writer) = &mut writer {
+            let rows = class
+                .canonical
+                .chunks(size)
+                .map(|row| row.iter().collect::<String>());
+            let solution = Solution::new()
+                .list("rows", rows)
+                .number("solutions", class.len())
+                .text("symmetry", &class.describe());
+            writer.write(&solution).unwrap();
             continue;
         }
-
-        println!("Found solution:");
-        for ii in 0..3 {
-            println!(
-                " {} {} {}",
-                perm[3 * ii],
-                perm[3 * ii + 1],
-                perm[3 * ii + 2]
-            );
+        println!("Found solution ({}):", class.describe());
+        for row in class.canonical.chunks(size) {
+            let row: Vec<String> = row.iter().map(|ch| ch.to_string()).collect();
+            println!(" {}", row.join(" "));
         }
     }
+    stats.report(stats_format);
 }
@@ -0,0 +1,113 @@
+// Distinct permutations of a multiset in lexicographic order. Unlike Heap's algorithm this never
+// produces the same arrangement twice when items repeat ("beermouth" has 9!/2 arrangements, not
+// 9!), and whole subtrees can be skipped once a prefix is known to be useless.
+
+pub struct MultisetPermutations<T> {
+    items: Vec<T>,
+    started: bool,
+    done: bool,
+}
+
+impl<T: Ord + Clone> MultisetPermutations<T> {
+    pub fn new(mut items: Vec<T>) -> MultisetPermutations<T> {
+        items.sort();
+        MultisetPermutations {
+            items,
+            started: false,
+            done: false,
+        }
+    }
+
+    // Skips every remaining permutation that starts with the first `len` items of the one just
+    // returned. Putting the rest in descending order makes it the last such permutation.
+    pub fn skip_prefix(&mut self, len: usize) {
+        if len == 0 {
+            self.done = true;
+            return;
+        }
+        if len < self.items.len() {
+            self.items[len..].sort_by(|a, b| b.cmp(a));
+        }
+    }
+}
+
+// Rearranges `items` into the next permutation in lexicographic order. Returns false if they were
+// already in the last one.
+fn next_permutation<T: Ord>(items: &mut [T]) -> bool {
+    if items.len() < 2 {
+        return false;
+    }
+    // Find the longest non-increasing suffix. The item just before it is the one to bump.
+    let mut ii = items.len() - 1;
+    while ii > 0 && items[ii - 1] >= items[ii] {
+        ii -= 1;
+    }
+    if ii == 0 {
+        return false;
+    }
+    // Swap it with the smallest item in the suffix that is larger than it.
+    let mut jj = items.len() - 1;
+    while items[jj] <= items[ii - 1] {
+        jj -= 1;
+    }
+    items.swap(ii - 1, jj);
+    items[ii..].reverse();
+    true
+}
+
+impl<T: Ord + Clone> Iterator for MultisetPermutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        if self.started && !next_permutation(&mut self.items) {
+            self.done = true;
+            return None;
+        }
+        self.started = true;
+        Some(self.items.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(perms: MultisetPermutations<char>) -> Vec<String> {
+        perms.map(|perm| perm.into_iter().collect()).collect()
+    }
+
+    #[test]
+    fn test_distinct() {
+        let perms = MultisetPermutations::new("bab".chars().collect());
+        assert_eq!(strings(perms), vec!["abb", "bab", "bba"]);
+        assert_eq!(
+            MultisetPermutations::new("beermouth".chars().collect()).count(),
+            181440
+        );
+        assert_eq!(MultisetPermutations::new(vec!['a']).count(), 1);
+        assert_eq!(MultisetPermutations::<char>::new(Vec::new()).count(), 1);
+    }
+
+    #[test]
+    fn test_skip_prefix() {
+        let mut perms = MultisetPermutations::new("abcc".chars().collect());
+        let mut seen = Vec::new();
+        while let Some(perm) = perms.next() {
+            let perm: String = perm.into_iter().collect();
+            // Nothing starting with "ac" or "b" is wanted.
+            if perm.starts_with("ac") {
+                perms.skip_prefix(2);
+            } else if perm.starts_with('b') {
+                perms.skip_prefix(1);
+            }
+            seen.push(perm);
+        }
+        assert_eq!(
+            seen,
+            vec!["abcc", "acbc", "bacc", "cabc", "cacb", "cbac", "cbca", "ccab", "ccba"]
+        );
+    }
+}
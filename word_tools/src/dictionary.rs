@@ -0,0 +1,92 @@
+// Loading word lists, plus the filters any puzzle can opt into from the command line.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use crate::flags;
+use crate::morphology::{remove_inflections, Inflection};
+
+pub const FILTER_USAGE: &str = "  --lowercase-only      Skip capitalized words (proper nouns)
+  --reject LIST         Skip inflected forms: s, ed, ing, er, est or all (comma separated)";
+
+// Reads one word per line from `path`.
+pub fn read_words(path: &str) -> Vec<String> {
+    let f = match File::open(path) {
+        Ok(f) => f,
+        Err(why) => panic!("Failed to open {}: {}", path, why),
+    };
+    BufReader::new(f).lines().map(|s| s.unwrap()).collect()
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WordFilter {
+    pub lowercase_only: bool,
+    // Inflected forms of other dictionary words to drop.
+    pub reject: Vec<Inflection>,
+}
+
+impl WordFilter {
+    // Handles --lowercase-only and --reject (see `flags`).
+    pub fn parse_arg<I>(&mut self, arg: &str, args: &mut I) -> Result<bool, String>
+    where
+        I: Iterator<Item = String>,
+    {
+        match arg {
+            "--lowercase-only" => self.lowercase_only = true,
+            "--reject" => self.reject = flags::named(arg, args, Inflection::parse_list)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    pub fn apply(&self, mut words: Vec<String>) -> Vec<String> {
+        if self.lowercase_only {
+            words.retain(|word| !word.chars().any(|ch| ch.is_uppercase()));
+        }
+        remove_inflections(words, &self.reject)
+    }
+}
+
+// Reads `path` and applies `filter` to it.
+pub fn load_words(path: &str, filter: &WordFilter) -> Vec<String> {
+    filter.apply(read_words(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_arg() {
+        let mut filter = WordFilter::default();
+        let mut args = vec!["s,ed".to_string(), "rest".to_string()].into_iter();
+        assert_eq!(filter.parse_arg("--reject", &mut args), Ok(true));
+        assert_eq!(filter.parse_arg("--lowercase-only", &mut args), Ok(true));
+        assert_eq!(filter.parse_arg("--other", &mut args), Ok(false));
+        assert_eq!(args.next(), Some("rest".to_string()));
+        assert_eq!(
+            filter,
+            WordFilter {
+                lowercase_only: true,
+                reject: vec![Inflection::Plural, Inflection::Past],
+            }
+        );
+        assert!(filter.parse_arg("--reject", &mut args).is_err());
+    }
+
+    #[test]
+    fn test_apply() {
+        let dict = words(&["Paris", "cat", "cats", "walk", "walked"]);
+        assert_eq!(WordFilter::default().apply(dict.clone()), dict);
+
+        let filter = WordFilter {
+            lowercase_only: true,
+            reject: vec![Inflection::Plural],
+        };
+        assert_eq!(filter.apply(dict), words(&["cat", "walk", "walked"]));
+    }
+}
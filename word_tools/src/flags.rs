@@ -0,0 +1,54 @@
+// Reading flag values for the options shared between the solvers. Each option type has a
+// `parse_arg(arg, args)` that returns Ok(false) if `arg` isn't one of its flags and otherwise
+// takes the flag's value, if it has one, from `args`. A solver offers each argument to them in
+// turn before looking at its own flags.
+
+// The value after `arg`.
+pub fn value<I>(arg: &str, args: &mut I) -> Result<String, String>
+where
+    I: Iterator<Item = String>,
+{
+    args.next()
+        .ok_or_else(|| format!("Missing value for {}", arg))
+}
+
+// The value after `arg` as a number.
+pub fn number<I>(arg: &str, args: &mut I) -> Result<usize, String>
+where
+    I: Iterator<Item = String>,
+{
+    value(arg, args)?
+        .parse()
+        .map_err(|why| format!("Bad value for {}: {}", arg, why))
+}
+
+// The value after `arg` as `parse` reads it, e.g. the name of a format.
+pub fn named<I, T, F>(arg: &str, args: &mut I, parse: F) -> Result<T, String>
+where
+    I: Iterator<Item = String>,
+    F: FnOnce(&str) -> Option<T>,
+{
+    let value = value(arg, args)?;
+    parse(&value).ok_or_else(|| format!("Unknown value for {}: {}", arg, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_values() {
+        let mut args = vec!["12".to_string(), "x".to_string(), "b".to_string()].into_iter();
+        assert_eq!(number("--count", &mut args), Ok(12));
+        assert_eq!(
+            number("--count", &mut args),
+            Err("Bad value for --count: invalid digit found in string".to_string())
+        );
+        let letter = |name: &str| name.chars().next().filter(|ch| *ch < 'c');
+        assert_eq!(named("--letter", &mut args, letter), Ok('b'));
+        assert_eq!(
+            value("--letter", &mut args),
+            Err("Missing value for --letter".to_string())
+        );
+    }
+}
@@ -0,0 +1,233 @@
+// Helpers for turning the solvers around to propose new puzzles. A puzzle is only worth proposing
+// if it has exactly one answer among words people know, so generators work from a list of common
+// words ranked by how common they are, and the proposals are ranked by the least common word in
+// the answer and then by how much searching it takes to solve.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::dictionary::read_words;
+use crate::flags;
+use crate::solution::{OutputFormat, Solution, SolutionWriter};
+
+pub const GENERATE_USAGE: &str =
+    "  --generate N          Propose up to N new puzzles with exactly one answer among --common
+  --common FILE         Common words, most common first, each optionally followed by a count
+  --common-limit N      Only use the N most common words";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GenerateOptions {
+    // How many puzzles to propose. None if not generating.
+    pub count: Option<usize>,
+    pub common: Option<String>,
+    pub common_limit: Option<usize>,
+}
+
+impl GenerateOptions {
+    // Handles --generate, --common and --common-limit (see `flags`).
+    pub fn parse_arg<I>(&mut self, arg: &str, args: &mut I) -> Result<bool, String>
+    where
+        I: Iterator<Item = String>,
+    {
+        match arg {
+            "--generate" => self.count = Some(flags::number(arg, args)?),
+            "--common" => self.common = Some(flags::value(arg, args)?),
+            "--common-limit" => self.common_limit = Some(flags::number(arg, args)?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    // Reads the --common words, cut down to --common-limit.
+    pub fn load(&self) -> Result<Commonness, String> {
+        let path = self
+            .common
+            .as_ref()
+            .ok_or_else(|| "--generate needs a --common word list".to_string())?;
+        let mut common = Commonness::from_lines(read_words(path));
+        if let Some(limit) = self.common_limit {
+            common.truncate(limit);
+        }
+        Ok(common)
+    }
+}
+
+// Words ranked from most to least common.
+#[derive(Clone, Debug, Default)]
+pub struct Commonness {
+    words: Vec<String>,
+    ranks: HashMap<String, usize>,
+}
+
+impl Commonness {
+    // Takes lines of "word" in order of commonness, or "word count" in any order. Only the first
+    // time a word appears counts.
+    pub fn from_lines<I>(lines: I) -> Commonness
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut counted: Vec<(String, Option<u64>)> = lines
+            .into_iter()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let word = fields.next()?.to_string();
+                Some((word, fields.next().and_then(|count| count.parse().ok())))
+            })
+            .collect();
+        // A stable sort keeps the listed order where there are no counts.
+        if counted.iter().all(|(_, count)| count.is_some()) {
+            counted.sort_by_key(|(_, count)| Reverse(*count));
+        }
+        let mut common = Commonness::default();
+        for (word, _) in counted {
+            if !common.ranks.contains_key(&word) {
+                common.ranks.insert(word.clone(), common.words.len());
+                common.words.push(word);
+            }
+        }
+        common
+    }
+
+    // Keeps the `len` most common words.
+    pub fn truncate(&mut self, len: usize) {
+        for word in self.words.iter().skip(len) {
+            self.ranks.remove(word);
+        }
+        self.words.truncate(len);
+    }
+
+    // The words, most common first.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    // 0 for the most common word.
+    pub fn rank(&self, word: &str) -> Option<usize> {
+        self.ranks.get(word).cloned()
+    }
+
+    // The rank of the least common of `words`. Words that aren't listed come after all of them.
+    pub fn rarest<'a, I>(&self, words: I) -> usize
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        words
+            .into_iter()
+            .map(|word| self.rank(word).unwrap_or(self.words.len()))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+// A proposed puzzle along with its answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Proposal {
+    pub statement: String,
+    pub answer: String,
+    // `Commonness::rarest` of the words in the answer.
+    pub rarest: usize,
+    // How much searching it takes to solve, e.g. the number of nodes in the search tree.
+    pub effort: usize,
+}
+
+impl Proposal {
+    pub fn to_solution(&self) -> Solution {
+        Solution::new()
+            .text("statement", &self.statement)
+            .text("answer", &self.answer)
+            .number("rarest", self.rarest)
+            .number("effort", self.effort)
+    }
+}
+
+// Puts the proposals whose answers use the most common words first, and the ones that take more
+// searching first among those.
+pub fn rank(proposals: &mut [Proposal]) {
+    proposals.sort_by_key(|proposal| (proposal.rarest, Reverse(proposal.effort)));
+}
+
+// "a" or "an" before `number` read out loud, as in "an 8-letter word".
+pub fn article(number: usize) -> &'static str {
+    if number == 11 || number == 18 || number.to_string().starts_with('8') {
+        "an"
+    } else {
+        "a"
+    }
+}
+
+// Ranks `proposals` and prints the first `count` of them.
+pub fn report(mut proposals: Vec<Proposal>, count: usize, output: OutputFormat) -> io::Result<()> {
+    rank(&mut proposals);
+    proposals.truncate(count);
+    if output != OutputFormat::Text {
+        let mut writer = SolutionWriter::new(output, io::stdout());
+        for proposal in &proposals {
+            writer.write(&proposal.to_solution())?;
+        }
+        return Ok(());
+    }
+    let mut out = io::stdout();
+    if proposals.is_empty() {
+        writeln!(out, "No puzzle with exactly one answer found")?;
+    }
+    for (ii, proposal) in proposals.iter().enumerate() {
+        writeln!(out, "{}. {}", ii + 1, proposal.statement)?;
+        writeln!(
+            out,
+            "   Answer: {} (rarest word #{}, search size {})",
+            proposal.answer,
+            proposal.rarest + 1,
+            proposal.effort
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_commonness() {
+        let mut common = Commonness::from_lines(lines(&["the", "of", "cat", "the", ""]));
+        assert_eq!(common.words(), &["the", "of", "cat"]);
+        assert_eq!(common.rank("cat"), Some(2));
+        assert_eq!(common.rarest(["of", "the"]), 1);
+        assert_eq!(common.rarest(["of", "zzz"]), 3);
+        common.truncate(2);
+        assert_eq!(common.rank("cat"), None);
+
+        let counted = Commonness::from_lines(lines(&["cat 5", "the 90", "of 20"]));
+        assert_eq!(counted.words(), &["the", "of", "cat"]);
+    }
+
+    #[test]
+    fn test_rank() {
+        let proposal = |rarest, effort| Proposal {
+            statement: String::new(),
+            answer: format!("{} {}", rarest, effort),
+            rarest,
+            effort,
+        };
+        let mut proposals = vec![proposal(5, 1), proposal(2, 3), proposal(2, 9)];
+        rank(&mut proposals);
+        let answers: Vec<&str> = proposals.iter().map(|p| p.answer.as_str()).collect();
+        assert_eq!(answers, vec!["2 9", "2 3", "5 1"]);
+        assert_eq!(article(8), "an");
+        assert_eq!(article(80), "an");
+        assert_eq!(article(7), "a");
+
+        let mut options = GenerateOptions::default();
+        let mut args = lines(&["3", "words.txt"]).into_iter();
+        assert_eq!(options.parse_arg("--generate", &mut args), Ok(true));
+        assert_eq!(options.parse_arg("--common", &mut args), Ok(true));
+        assert_eq!(options.count, Some(3));
+        assert_eq!(options.common.as_deref(), Some("words.txt"));
+        assert!(options.parse_arg("--common-limit", &mut args).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+// A multiset of the letters a-z stored as 26 counts. It's Copy and compares in a fixed number of
+// steps, so search loops can clone and check it freely where a HashMap<char, u8> would allocate.
+
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LetterBag {
+    counts: [u8; 26],
+}
+
+fn index(letter: char) -> Option<usize> {
+    if letter.is_ascii_lowercase() {
+        Some((letter as u8 - b'a') as usize)
+    } else {
+        None
+    }
+}
+
+impl LetterBag {
+    pub fn new() -> LetterBag {
+        LetterBag::default()
+    }
+
+    // The letters of `word`, or None if it has anything other than a-z in it.
+    pub fn from_word(word: &str) -> Option<LetterBag> {
+        let mut bag = LetterBag::new();
+        for letter in word.chars() {
+            if !bag.insert(letter) {
+                return None;
+            }
+        }
+        Some(bag)
+    }
+
+    pub fn count(&self, letter: char) -> u8 {
+        index(letter).map_or(0, |ii| self.counts[ii])
+    }
+
+    pub fn contains(&self, letter: char) -> bool {
+        self.count(letter) > 0
+    }
+
+    // The total number of letters.
+    pub fn len(&self) -> usize {
+        self.counts.iter().map(|&count| count as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().all(|&count| count == 0)
+    }
+
+    // The distinct letters in alphabetical order.
+    pub fn letters(&self) -> impl Iterator<Item = char> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count > 0)
+            .map(|(ii, _)| (b'a' + ii as u8) as char)
+    }
+
+    // Adds one `letter`. Returns false (leaving the bag alone) if it isn't a-z or the bag already
+    // holds as many of it as a count can.
+    pub fn insert(&mut self, letter: char) -> bool {
+        let count = index(letter).and_then(|ii| Some((ii, self.counts[ii].checked_add(1)?)));
+        match count {
+            Some((ii, count)) => {
+                self.counts[ii] = count;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Takes out one `letter`. Returns false if there wasn't one.
+    pub fn remove(&mut self, letter: char) -> bool {
+        match index(letter) {
+            Some(ii) if self.counts[ii] > 0 => {
+                self.counts[ii] -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Whether every letter of `self` appears at least as often in `other`.
+    pub fn is_subset(&self, other: &LetterBag) -> bool {
+        self.counts
+            .iter()
+            .zip(other.counts.iter())
+            .all(|(mine, theirs)| mine <= theirs)
+    }
+
+    // Removes the letters of `other`, or returns None if `other` isn't a subset of `self`.
+    pub fn subtract(&self, other: &LetterBag) -> Option<LetterBag> {
+        let mut out = *self;
+        for (count, &taken) in out.counts.iter_mut().zip(other.counts.iter()) {
+            *count = count.checked_sub(taken)?;
+        }
+        Some(out)
+    }
+
+    // Both bags' letters together, or None if there are more of a letter than a count can hold.
+    pub fn add(&self, other: &LetterBag) -> Option<LetterBag> {
+        let mut out = *self;
+        for (count, &added) in out.counts.iter_mut().zip(other.counts.iter()) {
+            *count = count.checked_add(added)?;
+        }
+        Some(out)
+    }
+
+    // The smallest bag that both bags are subsets of.
+    pub fn union(&self, other: &LetterBag) -> LetterBag {
+        let mut out = *self;
+        for (count, &theirs) in out.counts.iter_mut().zip(other.counts.iter()) {
+            *count = (*count).max(theirs);
+        }
+        out
+    }
+
+    // The letters of `self` left over once `other`'s are removed, ignoring any letters `self`
+    // doesn't have.
+    pub fn difference(&self, other: &LetterBag) -> LetterBag {
+        let mut out = *self;
+        for (count, &theirs) in out.counts.iter_mut().zip(other.counts.iter()) {
+            *count = count.saturating_sub(theirs);
+        }
+        out
+    }
+}
+
+// Prints the letters in alphabetical order, e.g. "beehmortu".
+impl fmt::Display for LetterBag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for letter in self.letters() {
+            for _ in 0..self.count(letter) {
+                write!(f, "{}", letter)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bag(word: &str) -> LetterBag {
+        LetterBag::from_word(word).unwrap()
+    }
+
+    #[test]
+    fn test_from_word() {
+        let beermouth = bag("beermouth");
+        assert_eq!(beermouth.count('e'), 2);
+        assert_eq!(beermouth.count('z'), 0);
+        assert_eq!(beermouth.len(), 9);
+        assert_eq!(beermouth.to_string(), "beehmortu");
+        assert_eq!(
+            beermouth.letters().collect::<String>(),
+            "behmortu".to_string()
+        );
+        assert!(LetterBag::from_word("o'clock").is_none());
+        assert!(LetterBag::from_word("Paris").is_none());
+        assert!(LetterBag::new().is_empty());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let most = "a".repeat(255);
+        assert_eq!(bag(&most).count('a'), 255);
+        assert!(LetterBag::from_word(&(most.clone() + "a")).is_none());
+        let mut full = bag(&most);
+        assert!(!full.insert('a'));
+        assert_eq!(full.count('a'), 255);
+        assert_eq!(full.add(&bag("a")), None);
+        assert_eq!(full.add(&bag("b")).map(|bag| bag.len()), Some(256));
+    }
+
+    #[test]
+    fn test_subset() {
+        let beermouth = bag("beermouth");
+        for word in ["bee", "bum", "but", "out"] {
+            assert!(bag(word).is_subset(&beermouth), "{}", word);
+        }
+        for word in ["boo", "oom", "eee"] {
+            assert!(!bag(word).is_subset(&beermouth), "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let beermouth = bag("beermouth");
+        assert_eq!(beermouth.subtract(&bag("bee")), Some(bag("rmouth")));
+        assert_eq!(beermouth.subtract(&bag("boo")), None);
+        assert_eq!(bag("bee").add(&bag("rmouth")), Some(beermouth));
+        assert_eq!(bag("bee").union(&bag("eel")), bag("beel"));
+        assert_eq!(bag("bee").difference(&bag("eye")), bag("b"));
+
+        let mut pool = bag("ab");
+        assert!(pool.remove('a'));
+        assert!(!pool.remove('a'));
+        assert!(pool.insert('c'));
+        assert!(!pool.insert('!'));
+        assert_eq!(pool, bag("bc"));
+    }
+}
@@ -0,0 +1,11 @@
+pub mod dictionary;
+pub mod flags;
+pub mod generate;
+pub mod letters;
+pub mod morphology;
+pub mod near;
+pub mod solution;
+pub mod stats;
+pub mod symmetry;
+pub mod trie;
+pub mod uniqueness;
@@ -0,0 +1,267 @@
+// Detects inflected forms (plurals, -ed, -ing, -er, -est) so that puzzles saying "no plurals" can
+// drop them. There's no part of speech information, so a word counts as inflected when stripping
+// a regular ending leaves another word in the dictionary.
+
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Inflection {
+    // -s and -es, which also covers verbs like "runs".
+    Plural,
+    Past,
+    Progressive,
+    Comparative,
+    Superlative,
+}
+
+impl Inflection {
+    pub fn all() -> Vec<Inflection> {
+        vec![
+            Inflection::Plural,
+            Inflection::Past,
+            Inflection::Progressive,
+            Inflection::Comparative,
+            Inflection::Superlative,
+        ]
+    }
+
+    pub fn parse(name: &str) -> Option<Inflection> {
+        match name {
+            "s" | "plural" => Some(Inflection::Plural),
+            "ed" | "past" => Some(Inflection::Past),
+            "ing" => Some(Inflection::Progressive),
+            "er" | "comparative" => Some(Inflection::Comparative),
+            "est" | "superlative" => Some(Inflection::Superlative),
+            _ => None,
+        }
+    }
+
+    // The short name `parse` accepts.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Inflection::Plural => "s",
+            Inflection::Past => "ed",
+            Inflection::Progressive => "ing",
+            Inflection::Comparative => "er",
+            Inflection::Superlative => "est",
+        }
+    }
+
+    // Parses a comma separated list of inflections. "all" means every inflection.
+    pub fn parse_list(names: &str) -> Option<Vec<Inflection>> {
+        if names == "all" {
+            return Some(Inflection::all());
+        }
+        names.split(',').map(Inflection::parse).collect()
+    }
+}
+
+// Stems shorter than this are ignored so that e.g. "need" isn't treated as "ne" + "ed" (or "nee"
+// + "d") and "his" isn't the plural of "hi".
+const MIN_STEM_LEN: usize = 3;
+
+// The candidate base forms of `word` for an ending that starts with a vowel (-ed, -ing, -er,
+// -est): "walked" -> "walk", "baked" -> "bake", "stopped" -> "stop", "carried" -> "carry".
+fn vowel_suffix_stems(word: &str, suffix: &str) -> Vec<String> {
+    let stem = match word.strip_suffix(suffix) {
+        Some(stem) if stem.len() >= MIN_STEM_LEN => stem,
+        _ => return Vec::new(),
+    };
+    let mut out = vec![stem.to_string(), format!("{}e", stem)];
+    let bytes = stem.as_bytes();
+    let last = bytes[bytes.len() - 1];
+    if bytes.len() > MIN_STEM_LEN && bytes[bytes.len() - 2] == last {
+        out.push(stem[..stem.len() - 1].to_string());
+    }
+    if last == b'i' && suffix != "ing" {
+        out.push(format!("{}y", &stem[..stem.len() - 1]));
+    }
+    out
+}
+
+// The candidate base forms of `word` for -s and -es: "cats" -> "cat", "boxes" -> "box",
+// "cities" -> "city".
+fn plural_stems(word: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    if word.ends_with("ss") {
+        return out;
+    }
+    if let Some(stem) = word.strip_suffix('s') {
+        out.push(stem.to_string());
+    }
+    if let Some(stem) = word.strip_suffix("es") {
+        out.push(stem.to_string());
+    }
+    if let Some(stem) = word.strip_suffix("ies") {
+        out.push(format!("{}y", stem));
+    }
+    out.retain(|stem| stem.len() >= MIN_STEM_LEN);
+    out
+}
+
+// If `word` is `inflection` applied to a word in `lexicon`, returns that base word.
+pub fn base_form(word: &str, inflection: Inflection, lexicon: &HashSet<String>) -> Option<String> {
+    let stems = match inflection {
+        Inflection::Plural => plural_stems(word),
+        Inflection::Past => vowel_suffix_stems(word, "ed"),
+        Inflection::Progressive => vowel_suffix_stems(word, "ing"),
+        Inflection::Comparative => vowel_suffix_stems(word, "er"),
+        Inflection::Superlative => vowel_suffix_stems(word, "est"),
+    };
+    // Adjectives have both forms, so "forest" needs a "forer" to count as a superlative of "fore".
+    let other = match inflection {
+        Inflection::Comparative => word.strip_suffix("er").map(|stem| format!("{}est", stem)),
+        Inflection::Superlative => word.strip_suffix("est").map(|stem| format!("{}er", stem)),
+        _ => None,
+    };
+    if other.is_some_and(|other| !lexicon.contains(&other)) {
+        return None;
+    }
+    stems
+        .into_iter()
+        .find(|stem| stem != word && lexicon.contains(stem))
+}
+
+// Finds the first of `inflections` that `word` is an inflected form of, along with its base word.
+pub fn inflection_of(
+    word: &str,
+    inflections: &[Inflection],
+    lexicon: &HashSet<String>,
+) -> Option<(Inflection, String)> {
+    inflections
+        .iter()
+        .find_map(|&inflection| base_form(word, inflection, lexicon).map(|base| (inflection, base)))
+}
+
+// Removes every word that is one of `inflections` of another word in `words`.
+pub fn remove_inflections(words: Vec<String>, inflections: &[Inflection]) -> Vec<String> {
+    if inflections.is_empty() {
+        return words;
+    }
+    let lexicon: HashSet<String> = words.iter().cloned().collect();
+    words
+        .into_iter()
+        .filter(|word| inflection_of(word, inflections, &lexicon).is_none())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lexicon(list: &[&str]) -> HashSet<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_plurals() {
+        let lex = lexicon(&["cat", "box", "city", "bus", "glass", "a"]);
+        let plural = |word| base_form(word, Inflection::Plural, &lex);
+        assert_eq!(plural("cats"), Some("cat".to_string()));
+        assert_eq!(plural("boxes"), Some("box".to_string()));
+        assert_eq!(plural("cities"), Some("city".to_string()));
+        assert_eq!(plural("glass"), None);
+        assert_eq!(plural("bus"), None);
+        assert_eq!(plural("as"), None);
+        assert_eq!(plural("dogs"), None);
+    }
+
+    #[test]
+    fn test_vowel_suffixes() {
+        let lex = lexicon(&[
+            "walk", "bake", "stop", "carry", "tall", "tallest", "nice", "nicer", "big", "biggest",
+            "happy", "happier", "b",
+        ]);
+        assert_eq!(
+            base_form("walked", Inflection::Past, &lex),
+            Some("walk".to_string())
+        );
+        assert_eq!(
+            base_form("baked", Inflection::Past, &lex),
+            Some("bake".to_string())
+        );
+        assert_eq!(
+            base_form("stopped", Inflection::Past, &lex),
+            Some("stop".to_string())
+        );
+        assert_eq!(
+            base_form("carried", Inflection::Past, &lex),
+            Some("carry".to_string())
+        );
+        assert_eq!(
+            base_form("baking", Inflection::Progressive, &lex),
+            Some("bake".to_string())
+        );
+        assert_eq!(
+            base_form("stopping", Inflection::Progressive, &lex),
+            Some("stop".to_string())
+        );
+        assert_eq!(
+            base_form("taller", Inflection::Comparative, &lex),
+            Some("tall".to_string())
+        );
+        assert_eq!(
+            base_form("bigger", Inflection::Comparative, &lex),
+            Some("big".to_string())
+        );
+        assert_eq!(
+            base_form("happiest", Inflection::Superlative, &lex),
+            Some("happy".to_string())
+        );
+        assert_eq!(
+            base_form("nicest", Inflection::Superlative, &lex),
+            Some("nice".to_string())
+        );
+        assert_eq!(base_form("bed", Inflection::Past, &lex), None);
+        assert_eq!(base_form("walker", Inflection::Past, &lex), None);
+    }
+
+    #[test]
+    fn test_short_stems() {
+        let lex = lexicon(&[
+            "hi", "ha", "ye", "nee", "see", "fee", "for", "fore", "hone", "eve", "ever", "fir",
+        ]);
+        for word in ["his", "has", "yes"] {
+            assert_eq!(base_form(word, Inflection::Plural, &lex), None);
+        }
+        for word in ["need", "seed", "feed"] {
+            assert_eq!(base_form(word, Inflection::Past, &lex), None);
+        }
+        for word in ["forest", "honest"] {
+            assert_eq!(base_form(word, Inflection::Superlative, &lex), None);
+        }
+        assert_eq!(base_form("ever", Inflection::Comparative, &lex), None);
+        // Three letter stems still count.
+        assert_eq!(
+            base_form("firs", Inflection::Plural, &lex),
+            Some("fir".to_string())
+        );
+    }
+
+    #[test]
+    fn test_remove_inflections() {
+        let words: Vec<String> = ["cat", "cats", "walk", "walked", "walking", "news"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            remove_inflections(words.clone(), &[Inflection::Plural]),
+            vec!["cat", "walk", "walked", "walking", "news"]
+        );
+        assert_eq!(
+            remove_inflections(words.clone(), &Inflection::all()),
+            vec!["cat", "walk", "news"]
+        );
+        assert_eq!(remove_inflections(words.clone(), &[]), words);
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            Inflection::parse_list("s,ing"),
+            Some(vec![Inflection::Plural, Inflection::Progressive])
+        );
+        assert_eq!(Inflection::parse_list("all"), Some(Inflection::all()));
+        assert_eq!(Inflection::parse_list("s,ly"), None);
+    }
+}
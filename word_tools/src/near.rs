@@ -0,0 +1,174 @@
+// Near misses: answers that break a few of a puzzle's rules. When a solver finds nothing the usual
+// reason is a word missing from the dictionary, so `--near K` lists everything that breaks at most
+// K rules instead, with the fewest broken first and dictionary gaps ahead of other kinds of rules.
+
+use std::io::{self, Write};
+
+use crate::flags;
+use crate::solution::{OutputFormat, Solution, SolutionWriter};
+
+pub const NEAR_USAGE: &str =
+    "  --near K              List answers that break up to K of the puzzle's rules (a word that isn't
+                        in the dictionary, a letter too many, ...), fewest broken first
+  --near-limit N        Print at most N of them (default 50)";
+
+// The kinds of rule an answer can break, in the order they're ranked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rule {
+    // A word that isn't in the dictionary.
+    Word,
+    // A letter the puzzle doesn't give, or one more of it than it gives.
+    Letters,
+    // Anything else, like a letter every word has to contain.
+    Other,
+}
+
+impl Rule {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::Word => "word",
+            Rule::Letters => "letters",
+            Rule::Other => "other",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub rule: Rule,
+    // What went wrong, e.g. "row 2 reads tex".
+    pub detail: String,
+}
+
+impl Violation {
+    pub fn new(rule: Rule, detail: String) -> Violation {
+        Violation { rule, detail }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NearMiss<T> {
+    pub answer: T,
+    pub violations: Vec<Violation>,
+}
+
+impl<T> NearMiss<T> {
+    // How far off the answer is: the number of broken rules, then which kinds they are.
+    pub fn closeness(&self) -> (usize, Vec<Rule>) {
+        let mut rules: Vec<Rule> = self.violations.iter().map(|v| v.rule).collect();
+        rules.sort();
+        (rules.len(), rules)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NearOptions {
+    // How many rules an answer may break. None if not looking for near misses.
+    pub allowed: Option<usize>,
+    pub limit: usize,
+}
+
+impl Default for NearOptions {
+    fn default() -> NearOptions {
+        NearOptions {
+            allowed: None,
+            limit: 50,
+        }
+    }
+}
+
+impl NearOptions {
+    // Handles --near and --near-limit (see `flags`).
+    pub fn parse_arg<I>(&mut self, arg: &str, args: &mut I) -> Result<bool, String>
+    where
+        I: Iterator<Item = String>,
+    {
+        match arg {
+            "--near" => self.allowed = Some(flags::number(arg, args)?),
+            "--near-limit" => self.limit = flags::number(arg, args)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+// Puts the closest answers first. Ties keep the order the solver found them in.
+pub fn rank<T>(misses: &mut [NearMiss<T>]) {
+    misses.sort_by_cached_key(|miss| miss.closeness());
+}
+
+// Ranks `misses` and prints the first `limit` of them, each answer as `describe` puts it followed
+// by the rules it breaks. Machine readable formats get the fields answer, broken, rules and
+// violations.
+pub fn report<T, F>(
+    mut misses: Vec<NearMiss<T>>,
+    describe: F,
+    limit: usize,
+    output: OutputFormat,
+) -> io::Result<()>
+where
+    F: Fn(&T) -> String,
+{
+    rank(&mut misses);
+    misses.truncate(limit);
+    if output != OutputFormat::Text {
+        let mut writer = SolutionWriter::new(output, io::stdout());
+        for miss in &misses {
+            let solution = Solution::new()
+                .text("answer", &describe(&miss.answer))
+                .number("broken", miss.violations.len())
+                .list("rules", miss.violations.iter().map(|v| v.rule.name()))
+                .list("violations", miss.violations.iter().map(|v| &v.detail));
+            writer.write(&solution)?;
+        }
+        return Ok(());
+    }
+    let mut out = io::stdout();
+    if misses.is_empty() {
+        writeln!(out, "No near misses found")?;
+    }
+    for miss in &misses {
+        match miss.violations.len() {
+            0 => writeln!(out, "{} (breaks nothing)", describe(&miss.answer))?,
+            1 => writeln!(out, "{} (breaks 1 rule)", describe(&miss.answer))?,
+            n => writeln!(out, "{} (breaks {} rules)", describe(&miss.answer), n)?,
+        }
+        for violation in &miss.violations {
+            writeln!(out, "  {}", violation.detail)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank() {
+        let miss = |answer, rules: &[Rule]| NearMiss {
+            answer,
+            violations: rules
+                .iter()
+                .map(|&rule| Violation::new(rule, String::new()))
+                .collect(),
+        };
+        let mut misses = vec![
+            miss("two", &[Rule::Other, Rule::Word]),
+            miss("letters", &[Rule::Letters]),
+            miss("exact", &[]),
+            miss("word", &[Rule::Word]),
+            miss("other", &[Rule::Other]),
+        ];
+        rank(&mut misses);
+        let answers: Vec<&str> = misses.iter().map(|miss| miss.answer).collect();
+        assert_eq!(answers, vec!["exact", "word", "letters", "other", "two"]);
+
+        let mut options = NearOptions::default();
+        let mut args = vec!["2".to_string(), "x".to_string()].into_iter();
+        assert_eq!(options.parse_arg("--near", &mut args), Ok(true));
+        assert_eq!(options.allowed, Some(2));
+        assert!(options.parse_arg("--near-limit", &mut args).is_err());
+        assert_eq!(options.parse_arg("--stats", &mut args), Ok(false));
+    }
+}
@@ -0,0 +1,253 @@
+// Machine readable output shared by the solvers. A solver describes each result as a `Solution`,
+// an ordered list of named fields, and a `SolutionWriter` prints it as one JSON object per line or
+// as a CSV row, so scripts can read results without scraping the text output.
+
+use std::io::{self, Write};
+
+use crate::flags;
+
+pub const OUTPUT_USAGE: &str =
+    "  --output-format F     text (default), jsonl for one JSON object per line, or csv";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    // The solver's usual output.
+    #[default]
+    Text,
+    JsonLines,
+    Csv,
+}
+
+impl OutputFormat {
+    pub fn parse(name: &str) -> Option<OutputFormat> {
+        match name {
+            "text" => Some(OutputFormat::Text),
+            "jsonl" => Some(OutputFormat::JsonLines),
+            "csv" => Some(OutputFormat::Csv),
+            _ => None,
+        }
+    }
+
+    // Handles --output-format (see `flags`).
+    pub fn parse_arg<I>(&mut self, arg: &str, args: &mut I) -> Result<bool, String>
+    where
+        I: Iterator<Item = String>,
+    {
+        if arg != "--output-format" {
+            return Ok(false);
+        }
+        *self = flags::named(arg, args, OutputFormat::parse)?;
+        Ok(true)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    Text(String),
+    Number(usize),
+    // Written as a JSON array, or space separated in CSV.
+    List(Vec<String>),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Solution {
+    pub fields: Vec<(&'static str, Value)>,
+}
+
+impl Solution {
+    pub fn new() -> Solution {
+        Solution::default()
+    }
+
+    pub fn text(mut self, name: &'static str, value: &str) -> Solution {
+        self.fields.push((name, Value::Text(value.to_string())));
+        self
+    }
+
+    pub fn number(mut self, name: &'static str, value: usize) -> Solution {
+        self.fields.push((name, Value::Number(value)));
+        self
+    }
+
+    pub fn list<I, S>(mut self, name: &'static str, values: I) -> Solution
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        let values = values.into_iter().map(|value| value.to_string()).collect();
+        self.fields.push((name, Value::List(values)));
+        self
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.fields.iter().map(|(name, _)| *name).collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    Value::Text(text) => json_string(text),
+                    Value::Number(number) => number.to_string(),
+                    Value::List(values) => {
+                        let values: Vec<String> = values.iter().map(|v| json_string(v)).collect();
+                        format!("[{}]", values.join(","))
+                    }
+                };
+                format!("{}:{}", json_string(name), value)
+            })
+            .collect();
+        format!("{{{}}}", fields.join(","))
+    }
+
+    pub fn to_csv(&self) -> String {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(_, value)| match value {
+                Value::Text(text) => csv_field(text),
+                Value::Number(number) => number.to_string(),
+                Value::List(values) => csv_field(&values.join(" ")),
+            })
+            .collect();
+        fields.join(",")
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for ch in text.chars() {
+        match ch {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\t' => out += "\\t",
+            ch if (ch as u32) < 0x20 => out += &format!("\\u{:04x}", ch as u32),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// Quotes a CSV field if it needs it.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+// Writes solutions in a machine readable format. For CSV a header row comes first, and every
+// solution after it has to have the same fields so that the file stays one table.
+pub struct SolutionWriter<W: Write> {
+    format: OutputFormat,
+    out: W,
+    header: Option<Vec<&'static str>>,
+}
+
+impl<W: Write> SolutionWriter<W> {
+    // `format` can't be `OutputFormat::Text`; the solvers print text themselves.
+    pub fn new(format: OutputFormat, out: W) -> SolutionWriter<W> {
+        assert_ne!(format, OutputFormat::Text);
+        SolutionWriter {
+            format,
+            out,
+            header: None,
+        }
+    }
+
+    pub fn write(&mut self, solution: &Solution) -> io::Result<()> {
+        match self.format {
+            OutputFormat::JsonLines => writeln!(self.out, "{}", solution.to_json()),
+            OutputFormat::Csv => {
+                let names = solution.names();
+                match &self.header {
+                    None => {
+                        writeln!(self.out, "{}", names.join(","))?;
+                        self.header = Some(names);
+                    }
+                    Some(header) if *header != names => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!(
+                                "CSV output can't mix {} rows with {} rows",
+                                names.join(","),
+                                header.join(",")
+                            ),
+                        ));
+                    }
+                    Some(_) => {}
+                }
+                writeln!(self.out, "{}", solution.to_csv())
+            }
+            OutputFormat::Text => unreachable!(),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+// A writer to stdout, or None for the text format.
+pub fn stdout_writer(format: OutputFormat) -> Option<SolutionWriter<io::Stdout>> {
+    match format {
+        OutputFormat::Text => None,
+        format => Some(SolutionWriter::new(format, io::stdout())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split() -> Solution {
+        Solution::new()
+            .text("word", "carpet")
+            .list("parts", ["car", "pet"])
+            .number("removed", 0)
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            split().to_json(),
+            r#"{"word":"carpet","parts":["car","pet"],"removed":0}"#
+        );
+        let quoted = Solution::new().text("note", "say \"hi\"\\\n");
+        assert_eq!(quoted.to_json(), r#"{"note":"say \"hi\"\\\n"}"#);
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(split().to_csv(), "carpet,car pet,0");
+        assert_eq!(
+            Solution::new().text("a", "x,y").text("b", "\"q\"").to_csv(),
+            "\"x,y\",\"\"\"q\"\"\""
+        );
+
+        let mut writer = SolutionWriter::new(OutputFormat::Csv, Vec::new());
+        writer.write(&split()).unwrap();
+        writer.write(&split()).unwrap();
+        assert!(writer.write(&Solution::new().number("count", 2)).is_err());
+        let out = String::from_utf8(writer.into_inner()).unwrap();
+        assert_eq!(
+            out,
+            "word,parts,removed\ncarpet,car pet,0\ncarpet,car pet,0\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_arg() {
+        let mut format = OutputFormat::default();
+        let mut args = vec!["csv".to_string()].into_iter();
+        assert_eq!(format.parse_arg("--other", &mut args), Ok(false));
+        assert_eq!(format.parse_arg("--output-format", &mut args), Ok(true));
+        assert_eq!(format, OutputFormat::Csv);
+        assert!(format.parse_arg("--output-format", &mut args).is_err());
+    }
+}
@@ -0,0 +1,204 @@
+// Counting what each stage of a search keeps. A solver records how many candidates went into and
+// came out of each stage along with how long it took, and `--stats` prints the funnel to stderr as
+// a table or as one JSON object per stage, so pruning order can be tuned without adding prints.
+
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::flags;
+use crate::solution::Solution;
+
+pub const STATS_USAGE: &str =
+    "  --stats F             Print candidates in and out, time and peak memory per stage to stderr:
+                        table or json";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StatsFormat {
+    #[default]
+    Off,
+    Table,
+    Json,
+}
+
+impl StatsFormat {
+    pub fn parse(name: &str) -> Option<StatsFormat> {
+        match name {
+            "table" => Some(StatsFormat::Table),
+            "json" => Some(StatsFormat::Json),
+            _ => None,
+        }
+    }
+
+    // Handles --stats (see `flags`).
+    pub fn parse_arg<I>(&mut self, arg: &str, args: &mut I) -> Result<bool, String>
+    where
+        I: Iterator<Item = String>,
+    {
+        if arg != "--stats" {
+            return Ok(false);
+        }
+        *self = flags::named(arg, args, StatsFormat::parse)?;
+        Ok(true)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stage {
+    pub name: String,
+    pub input: usize,
+    pub output: usize,
+    pub elapsed: Duration,
+    // The peak resident memory of the process once the stage was done, in kB, where known.
+    pub peak_kb: Option<usize>,
+}
+
+impl Stage {
+    pub fn to_solution(&self) -> Solution {
+        let solution = Solution::new()
+            .text("stage", &self.name)
+            .number("in", self.input)
+            .number("out", self.output)
+            .number("micros", self.elapsed.as_micros() as usize);
+        match self.peak_kb {
+            Some(peak_kb) => solution.number("peak_kb", peak_kb),
+            None => solution,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Stats {
+    pub stages: Vec<Stage>,
+    // When the stage being timed started.
+    started: Instant,
+}
+
+impl Default for Stats {
+    fn default() -> Stats {
+        Stats::new()
+    }
+}
+
+impl Stats {
+    pub fn new() -> Stats {
+        Stats {
+            stages: Vec::new(),
+            started: Instant::now(),
+        }
+    }
+
+    // Starts timing the next stage from now, leaving out anything since the last one.
+    pub fn restart(&mut self) {
+        self.started = Instant::now();
+    }
+
+    // Ends the stage being timed, which turned `input` candidates into `output`.
+    pub fn record(&mut self, name: &str, input: usize, output: usize) {
+        let elapsed = self.started.elapsed();
+        self.stages.push(Stage {
+            name: name.to_string(),
+            input,
+            output,
+            elapsed,
+            peak_kb: peak_memory_kb(),
+        });
+        // Reading the peak memory isn't part of the next stage.
+        self.started = Instant::now();
+    }
+
+    // One line per stage with the share of its input it kept.
+    pub fn table(&self) -> String {
+        let width = self
+            .stages
+            .iter()
+            .map(|stage| stage.name.len())
+            .chain(Some("stage".len()))
+            .max()
+            .unwrap();
+        let mut out = format!(
+            "{:<width$} {:>10} {:>10} {:>7} {:>10} {:>10}\n",
+            "stage",
+            "in",
+            "out",
+            "kept",
+            "time",
+            "peak mem",
+            width = width
+        );
+        for stage in &self.stages {
+            let kept = match stage.input {
+                0 => "-".to_string(),
+                input => format!("{:.1}%", 100.0 * stage.output as f64 / input as f64),
+            };
+            let peak = match stage.peak_kb {
+                Some(kb) => format!("{:.1} MB", kb as f64 / 1024.0),
+                None => "-".to_string(),
+            };
+            out += &format!(
+                "{:<width$} {:>10} {:>10} {:>7} {:>10} {:>10}\n",
+                stage.name,
+                stage.input,
+                stage.output,
+                kept,
+                format!("{:.1?}", stage.elapsed),
+                peak,
+                width = width
+            );
+        }
+        out
+    }
+
+    // One JSON object per stage.
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        for stage in &self.stages {
+            out += &stage.to_solution().to_json();
+            out.push('\n');
+        }
+        out
+    }
+
+    // Prints the stages to stderr in `format`, if it isn't `StatsFormat::Off`.
+    pub fn report(&self, format: StatsFormat) {
+        match format {
+            StatsFormat::Off => (),
+            StatsFormat::Table => eprint!("{}", self.table()),
+            StatsFormat::Json => eprint!("{}", self.to_json()),
+        }
+    }
+}
+
+// The most memory the process has had resident so far, in kB. Only known on Linux.
+pub fn peak_memory_kb() -> Option<usize> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let mut stats = Stats::new();
+        stats.record("words", 100, 25);
+        stats.record("grids", 0, 0);
+        assert_eq!(stats.stages.len(), 2);
+        let table = stats.table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("stage "));
+        assert!(lines[1].starts_with("words "));
+        assert!(lines[1].contains(" 25.0% "));
+        assert!(lines[2].contains(" - "));
+
+        let json = stats.to_json();
+        assert_eq!(json.lines().count(), 2);
+        assert!(json.starts_with(r#"{"stage":"words","in":100,"out":25,"micros":"#));
+
+        let mut format = StatsFormat::default();
+        let mut args = vec!["json".to_string()].into_iter();
+        assert_eq!(format.parse_arg("--stats", &mut args), Ok(true));
+        assert_eq!(format, StatsFormat::Json);
+    }
+}
@@ -0,0 +1,259 @@
+// The eight rotations and reflections of a square grid (the dihedral group), for reporting
+// solutions that are just turned or flipped copies of each other once. Grids are row major slices
+// of cells.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Symmetry {
+    Identity,
+    // Clockwise.
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    // Left and right swap.
+    FlipHorizontal,
+    // Top and bottom swap.
+    FlipVertical,
+    // Rows become columns.
+    Transpose,
+    // Reflection across the other diagonal.
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub fn all() -> [Symmetry; 8] {
+        [
+            Symmetry::Identity,
+            Symmetry::Rotate90,
+            Symmetry::Rotate180,
+            Symmetry::Rotate270,
+            Symmetry::FlipHorizontal,
+            Symmetry::FlipVertical,
+            Symmetry::Transpose,
+            Symmetry::AntiTranspose,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Symmetry::Identity => "identity",
+            Symmetry::Rotate90 => "rotate-90",
+            Symmetry::Rotate180 => "rotate-180",
+            Symmetry::Rotate270 => "rotate-270",
+            Symmetry::FlipHorizontal => "flip-horizontal",
+            Symmetry::FlipVertical => "flip-vertical",
+            Symmetry::Transpose => "transpose",
+            Symmetry::AntiTranspose => "anti-transpose",
+        }
+    }
+
+    // Where the cell at (`row`, `col`) of a `size` by `size` grid ends up.
+    pub fn map(&self, size: usize, row: usize, col: usize) -> (usize, usize) {
+        let last = size - 1;
+        match *self {
+            Symmetry::Identity => (row, col),
+            Symmetry::Rotate90 => (col, last - row),
+            Symmetry::Rotate180 => (last - row, last - col),
+            Symmetry::Rotate270 => (last - col, row),
+            Symmetry::FlipHorizontal => (row, last - col),
+            Symmetry::FlipVertical => (last - row, col),
+            Symmetry::Transpose => (col, row),
+            Symmetry::AntiTranspose => (last - col, last - row),
+        }
+    }
+
+    // The row major index each cell moves to.
+    pub fn destinations(&self, size: usize) -> Vec<usize> {
+        (0..size * size)
+            .map(|cell| {
+                let (row, col) = self.map(size, cell / size, cell % size);
+                row * size + col
+            })
+            .collect()
+    }
+
+    pub fn apply<T: Clone>(&self, size: usize, cells: &[T]) -> Vec<T> {
+        let mut out = cells.to_vec();
+        for (cell, dest) in self.destinations(size).into_iter().enumerate() {
+            out[dest] = cells[cell].clone();
+        }
+        out
+    }
+}
+
+// One solution along with the other solutions that are rotations or reflections of it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymmetryClass<T> {
+    // The smallest grid of the class.
+    pub canonical: Vec<T>,
+    // The symmetries that take `canonical` to a solution, including the ones that leave it as is.
+    pub variants: Vec<Symmetry>,
+    // The symmetries that leave `canonical` unchanged.
+    pub invariant: Vec<Symmetry>,
+}
+
+impl<T: Clone + PartialEq> SymmetryClass<T> {
+    // The number of distinct solutions in the class.
+    pub fn len(&self) -> usize {
+        self.variants.len() / self.invariant.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variants.is_empty()
+    }
+
+    // A short description such as "2 solutions, also transpose" or "1 solution, symmetric under
+    // transpose".
+    pub fn describe(&self) -> String {
+        let names = |list: &[Symmetry]| {
+            let names: Vec<&str> = list
+                .iter()
+                .filter(|&&symmetry| symmetry != Symmetry::Identity)
+                .map(|symmetry| symmetry.name())
+                .collect();
+            names.join(", ")
+        };
+        let distinct: Vec<Symmetry> = self
+            .variants
+            .iter()
+            .filter(|symmetry| !self.invariant.contains(symmetry))
+            .cloned()
+            .collect();
+        let mut out = format!(
+            "{} solution{}",
+            self.len(),
+            if self.len() == 1 { "" } else { "s" }
+        );
+        if !distinct.is_empty() {
+            out += &format!(", also {}", names(&distinct));
+        }
+        if self.invariant.len() > 1 {
+            out += &format!(", symmetric under {}", names(&self.invariant));
+        }
+        out
+    }
+}
+
+// The smallest of the grids `group` turns `cells` into.
+pub fn canonical<T: Clone + Ord>(size: usize, cells: &[T], group: &[Symmetry]) -> Vec<T> {
+    group
+        .iter()
+        .map(|symmetry| symmetry.apply(size, cells))
+        .min()
+        .unwrap_or_else(|| cells.to_vec())
+}
+
+// Groups `solutions` so that solutions which are images of each other under `group` are reported
+// together. Only grids that are actually in `solutions` end up in a class, so this is safe to use
+// with all eight symmetries even when the puzzle's constraints aren't symmetric. `group` has to be
+// closed under composition (a subgroup), which `Symmetry::all()` is.
+pub fn classify<T: Clone + Ord + Hash>(
+    size: usize,
+    solutions: &[Vec<T>],
+    group: &[Symmetry],
+) -> Vec<SymmetryClass<T>> {
+    let found: HashSet<&Vec<T>> = solutions.iter().collect();
+    let mut sorted: Vec<&Vec<T>> = found.iter().cloned().collect();
+    sorted.sort();
+    let mut assigned: HashSet<Vec<T>> = HashSet::new();
+    let mut out = Vec::new();
+    for solution in sorted {
+        if assigned.contains(solution) {
+            continue;
+        }
+        let mut class = SymmetryClass {
+            canonical: solution.clone(),
+            variants: Vec::new(),
+            invariant: Vec::new(),
+        };
+        for &symmetry in group {
+            let image = symmetry.apply(size, solution);
+            if image == *solution {
+                class.invariant.push(symmetry);
+            }
+            if found.contains(&image) {
+                class.variants.push(symmetry);
+                assigned.insert(image);
+            }
+        }
+        out.push(class);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(rows: &str) -> Vec<char> {
+        rows.chars().filter(|ch| *ch != '/').collect()
+    }
+
+    #[test]
+    fn test_apply() {
+        let grid = cells("abc/def/ghi");
+        let apply =
+            |symmetry: Symmetry| -> String { symmetry.apply(3, &grid).into_iter().collect() };
+        assert_eq!(apply(Symmetry::Identity), "abcdefghi");
+        assert_eq!(apply(Symmetry::Rotate90), "gdahebifc");
+        assert_eq!(apply(Symmetry::Rotate180), "ihgfedcba");
+        assert_eq!(apply(Symmetry::Rotate270), "cfibehadg");
+        assert_eq!(apply(Symmetry::FlipHorizontal), "cbafedihg");
+        assert_eq!(apply(Symmetry::FlipVertical), "ghidefabc");
+        assert_eq!(apply(Symmetry::Transpose), "adgbehcfi");
+        assert_eq!(apply(Symmetry::AntiTranspose), "ifchebgda");
+    }
+
+    #[test]
+    fn test_group_closed() {
+        // Every composition of two symmetries is another one.
+        let grid = cells("abcd/efgh/ijkl/mnop");
+        let images: HashSet<Vec<char>> =
+            Symmetry::all().iter().map(|s| s.apply(4, &grid)).collect();
+        assert_eq!(images.len(), 8);
+        for first in Symmetry::all().iter() {
+            for second in Symmetry::all().iter() {
+                let twice = second.apply(4, &first.apply(4, &grid));
+                assert!(images.contains(&twice));
+            }
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let square = cells("bit/ice/ten");
+        let other = cells("hem/obu/ter");
+        let other_t = Symmetry::Transpose.apply(3, &other);
+        let solutions = vec![other_t.clone(), square.clone(), other.clone()];
+        let classes = classify(3, &solutions, &Symmetry::all());
+        assert_eq!(classes.len(), 2);
+
+        // "bit/ice/ten" is its own transpose.
+        assert_eq!(classes[0].canonical, square);
+        assert_eq!(
+            classes[0].invariant,
+            vec![Symmetry::Identity, Symmetry::Transpose]
+        );
+        assert_eq!(classes[0].len(), 1);
+        assert_eq!(
+            classes[0].describe(),
+            "1 solution, symmetric under transpose"
+        );
+
+        assert_eq!(classes[1].canonical, other);
+        assert_eq!(
+            classes[1].variants,
+            vec![Symmetry::Identity, Symmetry::Transpose]
+        );
+        assert_eq!(classes[1].describe(), "2 solutions, also transpose");
+
+        // Restricted to the identity nothing is merged.
+        assert_eq!(classify(3, &solutions, &[Symmetry::Identity]).len(), 3);
+        assert_eq!(
+            canonical(3, &other_t, &Symmetry::all()),
+            canonical(3, &other, &Symmetry::all())
+        );
+    }
+}
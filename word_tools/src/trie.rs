@@ -0,0 +1,156 @@
+// A prefix tree over words made of the letters a-z, for searches that place one letter at a time
+// and need to know right away whether what they have so far can still become a word. Nodes are
+// referred to by index so that a search can keep one per line it is building.
+
+#[derive(Clone, Debug)]
+struct Node {
+    // Index of the child for each letter, 0 for none. The root is never a child.
+    children: [u32; 26],
+    word: bool,
+}
+
+impl Node {
+    fn new() -> Node {
+        Node {
+            children: [0; 26],
+            word: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Trie {
+    nodes: Vec<Node>,
+    words: usize,
+}
+
+impl Default for Trie {
+    fn default() -> Trie {
+        Trie::new()
+    }
+}
+
+fn index(letter: char) -> Option<usize> {
+    if letter.is_ascii_lowercase() {
+        Some(letter as usize - 'a' as usize)
+    } else {
+        None
+    }
+}
+
+impl Trie {
+    pub const ROOT: usize = 0;
+
+    pub fn new() -> Trie {
+        Trie {
+            nodes: vec![Node::new()],
+            words: 0,
+        }
+    }
+
+    // Builds a trie of the words that only use a-z. The rest are skipped.
+    pub fn from_words<'a, I>(words: I) -> Trie
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut trie = Trie::new();
+        for word in words {
+            trie.insert(word);
+        }
+        trie
+    }
+
+    // Adds `word`. Returns false if it was already there or has a letter outside a-z.
+    pub fn insert(&mut self, word: &str) -> bool {
+        if !word.chars().all(|letter| index(letter).is_some()) {
+            return false;
+        }
+        let mut node = Trie::ROOT;
+        for letter in word.chars() {
+            let slot = index(letter).unwrap();
+            node = match self.nodes[node].children[slot] {
+                0 => {
+                    self.nodes.push(Node::new());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children[slot] = child as u32;
+                    child
+                }
+                child => child as usize,
+            };
+        }
+        if self.nodes[node].word {
+            return false;
+        }
+        self.nodes[node].word = true;
+        self.words += 1;
+        true
+    }
+
+    // The number of words.
+    pub fn len(&self) -> usize {
+        self.words
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words == 0
+    }
+
+    pub fn child(&self, node: usize, letter: char) -> Option<usize> {
+        match self.nodes[node].children[index(letter)?] {
+            0 => None,
+            child => Some(child as usize),
+        }
+    }
+
+    // The letters that can follow `node`, in alphabetical order, along with where they lead.
+    pub fn children(&self, node: usize) -> impl Iterator<Item = (char, usize)> + '_ {
+        self.nodes[node]
+            .children
+            .iter()
+            .enumerate()
+            .filter(|(_, &child)| child != 0)
+            .map(|(slot, &child)| ((b'a' + slot as u8) as char, child as usize))
+    }
+
+    // Whether the letters leading to `node` are a word.
+    pub fn is_word(&self, node: usize) -> bool {
+        self.nodes[node].word
+    }
+
+    // The node reached by following `prefix` from the root, if any word starts with it.
+    pub fn find(&self, prefix: &str) -> Option<usize> {
+        prefix
+            .chars()
+            .try_fold(Trie::ROOT, |node, letter| self.child(node, letter))
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.find(word).is_some_and(|node| self.is_word(node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trie() {
+        let mut trie = Trie::from_words(["bit", "ice", "bite", "Ten", "ten"]);
+        assert_eq!(trie.len(), 4);
+        assert!(!trie.insert("ice"));
+        assert!(trie.contains("bit"));
+        assert!(trie.contains("bite"));
+        assert!(!trie.contains("bi"));
+        assert!(!trie.contains("Ten"));
+        assert!(trie.find("bi").is_some());
+        assert!(trie.find("bx").is_none());
+
+        let letters: Vec<char> = trie.children(Trie::ROOT).map(|(ch, _)| ch).collect();
+        assert_eq!(letters, vec!['b', 'i', 't']);
+        let bit = trie.find("bit").unwrap();
+        assert!(trie.is_word(bit));
+        let after: Vec<char> = trie.children(bit).map(|(ch, _)| ch).collect();
+        assert_eq!(after, vec!['e']);
+        assert_eq!(trie.child(bit, 'e'), trie.find("bite"));
+    }
+}
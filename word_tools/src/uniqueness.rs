@@ -0,0 +1,128 @@
+// Checking that a puzzle has exactly one answer, for setting puzzles rather than solving them.
+// A solver hands over its solutions lazily and only the first two are ever looked at, so the
+// search can stop as soon as the answer is known to be ambiguous.
+//
+// For an ambiguous puzzle the solvers also suggest hints, such as a word or letter to require,
+// taken from where the two answers differ. A hint that rules out one of them can still leave a
+// third answer, so `unique_hints` checks each one by solving again.
+
+use std::io;
+
+use crate::solution::{OutputFormat, Solution, SolutionWriter};
+
+pub const UNIQUE_USAGE: &str =
+    "  --check-unique        Report whether the puzzle has no answer, exactly one, or more than one
+                        (with two of them), and which extra options would make it unique";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Uniqueness<T> {
+    None,
+    Unique(T),
+    // Two of the answers.
+    Ambiguous(T, T),
+}
+
+impl<T> Uniqueness<T> {
+    // Takes no more than two of `solutions`.
+    pub fn check<I>(solutions: I) -> Uniqueness<T>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut solutions = solutions.into_iter();
+        match (solutions.next(), solutions.next()) {
+            (None, _) => Uniqueness::None,
+            (Some(first), None) => Uniqueness::Unique(first),
+            (Some(first), Some(second)) => Uniqueness::Ambiguous(first, second),
+        }
+    }
+
+    pub fn is_unique(&self) -> bool {
+        matches!(self, Uniqueness::Unique(_))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Uniqueness::None => "none",
+            Uniqueness::Unique(_) => "unique",
+            Uniqueness::Ambiguous(..) => "ambiguous",
+        }
+    }
+
+    // The answers found, none, one or two of them.
+    pub fn witnesses(&self) -> Vec<&T> {
+        match self {
+            Uniqueness::None => Vec::new(),
+            Uniqueness::Unique(first) => vec![first],
+            Uniqueness::Ambiguous(first, second) => vec![first, second],
+        }
+    }
+}
+
+// The `hints` that leave exactly one answer, where `check` solves the puzzle with a hint added.
+pub fn unique_hints<H, T, I, F>(hints: I, mut check: F) -> Vec<H>
+where
+    I: IntoIterator<Item = H>,
+    F: FnMut(&H) -> Uniqueness<T>,
+{
+    hints
+        .into_iter()
+        .filter(|hint| check(hint).is_unique())
+        .collect()
+}
+
+// Prints the verdict with each witness as `describe` puts it, followed by the `suggestions`
+// (command line options) that would make an ambiguous puzzle unique. Machine readable formats get
+// a single solution with the fields verdict, witnesses and suggestions.
+pub fn report<T, F>(
+    uniqueness: &Uniqueness<T>,
+    describe: F,
+    suggestions: &[String],
+    output: OutputFormat,
+) -> io::Result<()>
+where
+    F: Fn(&T) -> String,
+{
+    let witnesses: Vec<String> = uniqueness.witnesses().into_iter().map(describe).collect();
+    if output != OutputFormat::Text {
+        let solution = Solution::new()
+            .text("verdict", uniqueness.name())
+            .list("witnesses", &witnesses)
+            .list("suggestions", suggestions);
+        return SolutionWriter::new(output, io::stdout()).write(&solution);
+    }
+    println!("{}", uniqueness.name());
+    for witness in &witnesses {
+        println!("  {}", witness);
+    }
+    if let Uniqueness::Ambiguous(..) = uniqueness {
+        if suggestions.is_empty() {
+            println!("No single extra option makes the answer unique");
+        } else {
+            println!("Any one of these makes the answer unique:");
+            for suggestion in suggestions {
+                println!("  {}", suggestion);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert_eq!(Uniqueness::check(Vec::<u32>::new()), Uniqueness::None);
+        assert_eq!(Uniqueness::check(vec![7]), Uniqueness::Unique(7));
+        // Only two are taken from an endless supply.
+        let ambiguous = Uniqueness::check(1..);
+        assert_eq!(ambiguous, Uniqueness::Ambiguous(1, 2));
+        assert_eq!(ambiguous.name(), "ambiguous");
+        assert_eq!(ambiguous.witnesses(), vec![&1, &2]);
+        assert!(!ambiguous.is_unique());
+
+        let hints = unique_hints(0..4, |&hint| Uniqueness::check(hint..3));
+        assert_eq!(hints, vec![2]);
+    }
+}